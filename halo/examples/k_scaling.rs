@@ -0,0 +1,59 @@
+//! Measures keygen, prove and verify times of a fixed small circuit padded to
+//! 2^k rows, for k from 4 to 16:
+//!
+//! ```bash
+//! cargo run --release --example k_scaling
+//! ```
+//!
+//! Keygen and prove time grow with 2^k, since every column is committed over
+//! all rows regardless of how many are used. Verify time grows much more
+//! slowly, but it is not constant: with the inner product argument the verifier
+//! folds the commitment key itself, which is linear in 2^k. The padded circuit
+//! also reads its zero coefficients from the instance, which the verifier
+//! commits to as well.
+
+use std::time::Instant;
+
+use halo2_proofs::circuit::Value;
+use halo2_proofs::pasta::Fp;
+use halo_v::polynomial::{eval_generic, PolynomialCircuit};
+use halo_v::proof::{minimal_k, prove, setup, verify};
+
+fn main() {
+    // y = x^3 + x + 5, padded with zero coefficients up to 2^k rows
+    let coeffs = [5, 1, 0, 1].map(Fp::from).to_vec();
+    let x = Fp::from(3);
+    let y = eval_generic(&coeffs, x);
+    let unpadded = PolynomialCircuit {
+        coeffs,
+        x: Value::known(x),
+        pad_to_k: None,
+    };
+    let min_k = minimal_k(&unpadded, &[y]).expect("the circuit does not fit");
+
+    println!(" k | keygen     | prove      | verify");
+    for k in min_k.max(4)..=16 {
+        let circuit = PolynomialCircuit {
+            pad_to_k: Some(k),
+            ..unpadded.clone()
+        };
+        let public = circuit.instance(y).unwrap();
+
+        let start = Instant::now();
+        let (params, pk) = setup(k, &circuit).unwrap();
+        let keygen = start.elapsed();
+
+        let start = Instant::now();
+        let proof = prove(&params, &pk, circuit.clone(), &public).unwrap();
+        let proving = start.elapsed();
+
+        let start = Instant::now();
+        verify(&params, pk.get_vk(), &proof, &public).unwrap();
+        let verifying = start.elapsed();
+
+        println!(
+            "{:2} | {:10.2?} | {:10.2?} | {:.2?}",
+            k, keygen, proving, verifying
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! Prints the minimal `k` and the proof size of `PolynomialCircuit` for
+//! degrees 1 to 10:
+//!
+//! ```bash
+//! cargo run --release --example proof_size
+//! ```
+//!
+//! The number of columns is fixed, so the proof size only depends on `k` and
+//! steps up each time the degree pushes the circuit past a power of two.
+
+use halo2_proofs::circuit::Value;
+use halo2_proofs::pasta::Fp;
+use halo_v::polynomial::{eval_generic, PolynomialCircuit};
+use halo_v::proof::{minimal_k, prove, setup, verify};
+
+fn main() {
+    let x = Fp::from(3);
+
+    println!("degree | k | proof size");
+    for degree in 1..=10 {
+        let coeffs = vec![Fp::one(); degree + 1];
+        let public = vec![eval_generic(&coeffs, x)];
+        let circuit = PolynomialCircuit {
+            coeffs,
+            x: Value::known(x),
+            pad_to_k: None,
+        };
+
+        let k = minimal_k(&circuit, &public).expect("the circuit does not fit");
+        let (params, pk) = setup(k, &circuit).unwrap();
+        let proof = prove(&params, &pk, circuit, &public).unwrap();
+        verify(&params, pk.get_vk(), &proof, &public).unwrap();
+
+        println!("{:>6} | {} | {}", degree, k, proof.len());
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use halo2_proofs::pasta::group::ff::PrimeField;
+use halo2_proofs::pasta::{EqAffine, Fp};
+use halo2_proofs::plonk::{keygen_vk, Circuit, Error, VerifyingKey};
+use halo2_proofs::poly::commitment::Params;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::proof::verify;
+
+/// Returns the digest identifying a verifying key. It hashes the same pinned
+/// form that halo2 absorbs into the transcript, so equal keys share a digest.
+pub fn vk_digest(vk: &VerifyingKey<EqAffine>) -> [u8; 32] {
+    let hash = blake2b_simd::Params::new()
+        .hash_length(32)
+        .personal(b"Halo2-Vk-Digest")
+        .hash(format!("{:?}", vk.pinned()).as_bytes());
+
+    hash.as_bytes().try_into().unwrap()
+}
+
+/// Verifying keys known to this verifier, with the params they were made for.
+/// halo2 cannot deserialize a verifying key, so keys are registered in process.
+#[derive(Default)]
+pub struct VkStore {
+    keys: HashMap<[u8; 32], (Params<EqAffine>, VerifyingKey<EqAffine>)>,
+}
+
+impl VkStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a verifying key and returns its digest.
+    pub fn insert(&mut self, params: Params<EqAffine>, vk: VerifyingKey<EqAffine>) -> [u8; 32] {
+        let digest = vk_digest(&vk);
+        self.keys.insert(digest, (params, vk));
+        digest
+    }
+
+    pub fn get(&self, digest: &[u8; 32]) -> Option<&(Params<EqAffine>, VerifyingKey<EqAffine>)> {
+        self.keys.get(digest)
+    }
+}
+
+/// Errors reported when verifying a [`Halo2Bundle`].
+#[derive(Debug)]
+pub enum BundleError {
+    /// No verifying key with this digest is in the store.
+    UnknownKey([u8; 32]),
+    /// The proof does not verify.
+    Proof(Error),
+}
+
+/// A proof with everything needed to check it except the verifying key, which
+/// is referenced by digest. Public inputs are serialized as canonical 32-byte
+/// little-endian encodings.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Halo2Bundle {
+    pub proof: Vec<u8>,
+    #[serde(serialize_with = "serialize_fps", deserialize_with = "deserialize_fps")]
+    pub public: Vec<Fp>,
+    pub vk_digest: [u8; 32],
+}
+
+impl Halo2Bundle {
+    /// Verifies the proof with the key from `store` matching `vk_digest`.
+    pub fn verify(&self, store: &VkStore) -> Result<(), BundleError> {
+        let (params, vk) = store
+            .get(&self.vk_digest)
+            .ok_or(BundleError::UnknownKey(self.vk_digest))?;
+
+        verify(params, vk, &self.proof, &self.public).map_err(BundleError::Proof)
+    }
+}
+
+fn serialize_fps<S: Serializer>(fps: &[Fp], serializer: S) -> Result<S::Ok, S::Error> {
+    let reprs: Vec<[u8; 32]> = fps.iter().map(|fp| fp.to_repr()).collect();
+    reprs.serialize(serializer)
+}
+
+fn deserialize_fps<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Fp>, D::Error> {
+    let reprs = Vec::<[u8; 32]>::deserialize(deserializer)?;
+    reprs
+        .into_iter()
+        .map(|repr| {
+            Option::from(Fp::from_repr(repr))
+                .ok_or_else(|| serde::de::Error::custom("non-canonical field element"))
+        })
+        .collect()
+}
+
+const PARAMS_ENTRY: &str = "params.bin";
+const VK_DIGEST_ENTRY: &str = "vk_digest.bin";
+
+/// Writes what a verifier needs into a tar archive at `path`: the params and
+/// the digest of the verifying key. halo2 cannot serialize the key itself, so
+/// [`import_verifier_archive`] regenerates it from the circuit and checks it
+/// against the digest.
+pub fn export_verifier_archive(
+    path: &Path,
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+) -> io::Result<()> {
+    let mut params_bytes = vec![];
+    params.write(&mut params_bytes)?;
+
+    let mut archive = tar::Builder::new(File::create(path)?);
+    append_entry(&mut archive, PARAMS_ENTRY, &params_bytes)?;
+    append_entry(&mut archive, VK_DIGEST_ENTRY, &vk_digest(vk))?;
+    archive.into_inner()?;
+
+    Ok(())
+}
+
+/// Reads an archive written by [`export_verifier_archive`] and rebuilds the
+/// verifying key of `circuit`, failing if its digest differs from the archived one.
+pub fn import_verifier_archive<C: Circuit<Fp>>(
+    path: &Path,
+    circuit: &C,
+) -> io::Result<(Params<EqAffine>, VerifyingKey<EqAffine>, [u8; 32])> {
+    let mut params = None;
+    let mut digest = None;
+    for entry in tar::Archive::new(File::open(path)?).entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        match name.as_str() {
+            PARAMS_ENTRY => params = Some(Params::read(&mut entry)?),
+            VK_DIGEST_ENTRY => {
+                let mut bytes = [0; 32];
+                entry.read_exact(&mut bytes)?;
+                digest = Some(bytes);
+            }
+            _ => {}
+        }
+    }
+
+    let missing = |name| io::Error::new(io::ErrorKind::InvalidData, format!("no {}", name));
+    let params = params.ok_or_else(|| missing(PARAMS_ENTRY))?;
+    let digest = digest.ok_or_else(|| missing(VK_DIGEST_ENTRY))?;
+
+    let vk = keygen_vk(&params, circuit)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", err)))?;
+    if vk_digest(&vk) != digest {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the circuit does not match the archived verifying key",
+        ));
+    }
+
+    Ok((params, vk, digest))
+}
+
+fn append_entry(archive: &mut tar::Builder<File>, name: &str, data: &[u8]) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    archive.append_data(&mut header, name, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial::PolynomialCircuit;
+    use crate::proof::{prove, setup};
+    use halo2_proofs::circuit::Value;
+
+    #[test]
+    fn test_bundle_json_round_trip() {
+        let circuit = PolynomialCircuit {
+            coeffs: [5, 1, 0, 1].map(Fp::from).to_vec(),
+            x: Value::known(Fp::from(3)),
+            pad_to_k: None,
+        };
+        let public = vec![Fp::from(35)];
+
+        let (params, pk) = setup(5, &circuit).unwrap();
+        let proof = prove(&params, &pk, circuit, &public).unwrap();
+
+        let mut store = VkStore::new();
+        let bundle = Halo2Bundle {
+            proof,
+            public,
+            vk_digest: store.insert(params, pk.get_vk().clone()),
+        };
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        let decoded: Halo2Bundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, bundle);
+        decoded.verify(&store).unwrap();
+
+        let mut wrong = decoded.clone();
+        wrong.public = vec![Fp::from(36)];
+        assert!(matches!(wrong.verify(&store), Err(BundleError::Proof(_))));
+
+        assert!(matches!(
+            decoded.verify(&VkStore::new()),
+            Err(BundleError::UnknownKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_verifier_archive() {
+        let circuit = PolynomialCircuit {
+            coeffs: [5, 1, 0, 1].map(Fp::from).to_vec(),
+            x: Value::known(Fp::from(3)),
+            pad_to_k: None,
+        };
+        let public = vec![Fp::from(35)];
+
+        let (params, pk) = setup(5, &circuit).unwrap();
+        let proof = prove(&params, &pk, circuit.clone(), &public).unwrap();
+
+        let path = std::env::temp_dir().join(format!("halo_v_verifier_{}.tar", std::process::id()));
+        export_verifier_archive(&path, &params, pk.get_vk()).unwrap();
+
+        let (params, vk, digest) = import_verifier_archive(&path, &circuit).unwrap();
+        assert_eq!(digest, vk_digest(pk.get_vk()));
+        verify(&params, &vk, &proof, &public).unwrap();
+
+        // another polynomial has another verifying key
+        let other = PolynomialCircuit {
+            coeffs: [5, 2, 0, 1].map(Fp::from).to_vec(),
+            x: Value::unknown(),
+            pad_to_k: None,
+        };
+        assert!(import_verifier_archive(&path, &other).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
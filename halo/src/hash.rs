@@ -0,0 +1,134 @@
+use halo2_gadgets::poseidon::primitives::{self as poseidon, ConstantLength, P128Pow5T3};
+use halo2_gadgets::poseidon::{Hash, Pow5Chip, Pow5Config};
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::{Circuit, ConstraintSystem, Error};
+
+use crate::{FieldChip, FieldConfig, MyCircuit, Number, NumbericInstructions};
+
+/// Hashes the values as a Poseidon chain: starting from 0, each value is
+/// absorbed with `acc = Poseidon(acc, x)`. This is the reference for
+/// [`WitnessHashChip::expose_witness_hash`].
+pub fn witness_hash(xs: &[Fp]) -> Fp {
+    xs.iter().fold(Fp::zero(), |acc, x| {
+        poseidon::Hash::<_, P128Pow5T3, ConstantLength<2>, 3, 2>::init().hash([acc, *x])
+    })
+}
+
+#[derive(Clone, Debug)]
+pub struct WitnessHashConfig {
+    field: FieldConfig,
+    poseidon: Pow5Config<Fp, 3, 2>,
+}
+
+/// The field chip extended with a Poseidon chip. The Poseidon columns are only
+/// added to circuits that configure this chip.
+pub struct WitnessHashChip {
+    config: WitnessHashConfig,
+}
+
+impl WitnessHashChip {
+    pub fn new(config: WitnessHashConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<Fp>) -> WitnessHashConfig {
+        let field = MyCircuit::configure(meta);
+
+        let state = [(); 3].map(|_| meta.advice_column());
+        let partial_sbox = meta.advice_column();
+        let rc_a = [(); 3].map(|_| meta.fixed_column());
+        let rc_b = [(); 3].map(|_| meta.fixed_column());
+        let poseidon = Pow5Chip::configure::<P128Pow5T3>(meta, state, partial_sbox, rc_a, rc_b);
+
+        WitnessHashConfig { field, poseidon }
+    }
+
+    pub fn field(&self) -> FieldChip {
+        FieldChip::new(self.config.field.clone())
+    }
+
+    /// Hashes the cells with [`witness_hash`] and constrains the digest to
+    /// instance[row].
+    pub fn expose_witness_hash(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        nums: &[Number],
+        row: usize,
+    ) -> Result<(), Error> {
+        let field = self.field();
+
+        let mut acc = field.load_constant(layouter.namespace(|| "initial hash"), Fp::zero())?;
+        for (i, num) in nums.iter().enumerate() {
+            let chip = Pow5Chip::construct(self.config.poseidon.clone());
+            let hasher = Hash::<_, _, P128Pow5T3, ConstantLength<2>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| format!("init hash {}", i)),
+            )?;
+            let digest = hasher.hash(
+                layouter.namespace(|| format!("absorb {}", i)),
+                [acc.0, num.0.clone()],
+            )?;
+            acc = Number(digest);
+        }
+
+        field.expose_public(layouter.namespace(|| "expose hash"), acc, row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::circuit::{SimpleFloorPlanner, Value};
+    use halo2_proofs::dev::MockProver;
+
+    #[derive(Default)]
+    struct WitnessHashCircuit {
+        xs: Vec<Value<Fp>>,
+    }
+
+    impl Circuit<Fp> for WitnessHashCircuit {
+        type Config = WitnessHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                xs: vec![Value::unknown(); self.xs.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> WitnessHashConfig {
+            WitnessHashChip::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: WitnessHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = WitnessHashChip::new(config);
+
+            let xs = chip
+                .field()
+                .load_private_many(layouter.namespace(|| "load xs"), &self.xs)?;
+            chip.expose_witness_hash(layouter.namespace(|| "hash xs"), &xs, 0)
+        }
+    }
+
+    #[test]
+    fn test_expose_witness_hash() {
+        let k = 10;
+        let xs = [3, 9, 27].map(Fp::from);
+        let circuit = WitnessHashCircuit {
+            xs: xs.iter().copied().map(Value::known).collect(),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![witness_hash(&xs)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // the order of the witness is part of the hash
+        let swapped = [9, 3, 27].map(Fp::from);
+        let prover = MockProver::run(k, &circuit, vec![vec![witness_hash(&swapped)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
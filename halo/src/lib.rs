@@ -0,0 +1,2629 @@
+use halo2_proofs::circuit::{AssignedCell, Chip, Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::group::ff::{Field, PrimeField};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::{
+    Advice, Circuit, Column, ConstraintSystem, Error, Expression, Fixed, Instance, Selector,
+};
+use halo2_proofs::poly::Rotation;
+
+pub mod bundle;
+#[cfg(feature = "poseidon")]
+pub mod hash;
+pub mod polynomial;
+pub mod proof;
+
+pub trait NumbericInstructions {
+    type Num;
+
+    fn load_private(&self, layouter: impl Layouter<Fp>, x: Value<Fp>) -> Result<Self::Num, Error>;
+    fn load_private_many(
+        &self,
+        layouter: impl Layouter<Fp>,
+        xs: &[Value<Fp>],
+    ) -> Result<Vec<Self::Num>, Error>;
+    fn load_constant(&self, layouter: impl Layouter<Fp>, x: Fp) -> Result<Self::Num, Error>;
+    fn load_constants(
+        &self,
+        layouter: impl Layouter<Fp>,
+        xs: &[Fp],
+    ) -> Result<Vec<Self::Num>, Error>;
+    fn load_instance(&self, layouter: impl Layouter<Fp>, row: usize) -> Result<Self::Num, Error>;
+
+    fn witness(
+        &self,
+        layouter: impl Layouter<Fp>,
+        f: impl FnOnce() -> Value<Fp>,
+    ) -> Result<Self::Num, Error>;
+
+    fn mul(
+        &self,
+        layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error>;
+
+    fn add(
+        &self,
+        layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error>;
+
+    fn sub(
+        &self,
+        layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error>;
+
+    fn neg(&self, layouter: impl Layouter<Fp>, a: Self::Num) -> Result<Self::Num, Error>;
+
+    fn mul_add(
+        &self,
+        layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+        c: Self::Num,
+    ) -> Result<Self::Num, Error>;
+
+    fn square_add_const(
+        &self,
+        layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        c: Fp,
+    ) -> Result<Self::Num, Error>;
+
+    fn select(
+        &self,
+        layouter: impl Layouter<Fp>,
+        cond: Self::Num,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error>;
+
+    fn range_check(
+        &self,
+        layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        bits: usize,
+    ) -> Result<(), Error>;
+
+    fn dot(
+        &self,
+        layouter: impl Layouter<Fp>,
+        a: &[Self::Num],
+        b: &[Self::Num],
+    ) -> Result<Self::Num, Error>;
+
+    fn assert_equal(
+        &self,
+        layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<(), Error>;
+
+    fn expose_public(
+        &self,
+        layouter: impl Layouter<Fp>,
+        num: Self::Num,
+        row: usize,
+    ) -> Result<(), Error>;
+}
+
+#[derive(Clone, Debug)]
+pub struct FieldConfig {
+    advice: [Column<Advice>; 2],
+    instance: Column<Instance>,
+    constant: Column<Fixed>,
+    s_mul: Selector,
+    s_add: Selector,
+    s_sub: Selector,
+    s_mul_add: Selector,
+    s_square_add_const: Selector,
+    s_select: Selector,
+    s_range: Selector,
+}
+
+pub struct FieldChip {
+    config: FieldConfig,
+}
+
+impl Chip<Fp> for FieldChip {
+    type Config = FieldConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl FieldChip {
+    pub fn new(config: FieldConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn configure(
+        meta: &mut ConstraintSystem<Fp>,
+        advice: [Column<Advice>; 2],
+        instance: Column<Instance>,
+        constant: Column<Fixed>,
+    ) -> FieldConfig {
+        meta.enable_constant(constant);
+        meta.enable_equality(instance);
+
+        for column in &advice {
+            meta.enable_equality(*column);
+        }
+
+        let s_mul = meta.selector();
+        let s_add = meta.selector();
+        meta.create_gate("mul|add", |mete| {
+            //   y = x^3 + x + 5
+            //  +--------+------+-------+-------+
+            //  | a_0    | a_1  | s_mul | s_add |
+            //  +--------+------+-------+-------+
+            //  |  x     |  x   |   1   |   0   |
+            //  | x^2    |  x   |   1   |   0   |
+            //  | x^3    |  x   |   0   |   1   |
+            //  | x^3+x  |  5   |   0   |   1   |
+            //  +--------+------+-------+-------+
+
+            let a_0 = mete.query_advice(advice[0], Rotation::cur());
+            let a_1 = mete.query_advice(advice[1], Rotation::cur());
+            let out = mete.query_advice(advice[0], Rotation::next());
+
+            let s_mul = mete.query_selector(s_mul);
+            let s_add = mete.query_selector(s_add);
+
+            // if s_mul = 0, any value is allowed in a_0, a_1, and out.
+            // if s_mul != 0, this constrains a_0 * a_1 = out.
+            let s_1 = s_mul * (a_0.clone() * a_1.clone() - out.clone());
+
+            // if s_add = 0, any value is allowed in a_0, a_1, and out.
+            // if s_add != 0, this constrains a_0 + a_1 = out.
+            let s_2 = s_add * (a_0 + a_1 - out);
+
+            vec![s_1, s_2]
+        });
+
+        let s_sub = meta.selector();
+        meta.create_gate("sub", |meta| {
+            let a_0 = meta.query_advice(advice[0], Rotation::cur());
+            let a_1 = meta.query_advice(advice[1], Rotation::cur());
+            let out = meta.query_advice(advice[0], Rotation::next());
+            let s_sub = meta.query_selector(s_sub);
+
+            // if s_sub != 0, this constrains a_0 - a_1 = out.
+            vec![s_sub * (a_0 - a_1 - out)]
+        });
+
+        let s_mul_add = meta.selector();
+        meta.create_gate("mul_add", |meta| {
+            //  +-----------+-----+-----------+
+            //  | a_0       | a_1 | s_mul_add |
+            //  +-----------+-----+-----------+
+            //  | a         | b   |     1     |
+            //  | a * b + c | c   |     0     |
+            //  +-----------+-----+-----------+
+            let a_0 = meta.query_advice(advice[0], Rotation::cur());
+            let a_1 = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_advice(advice[1], Rotation::next());
+            let out = meta.query_advice(advice[0], Rotation::next());
+            let s_mul_add = meta.query_selector(s_mul_add);
+
+            // if s_mul_add != 0, this constrains a_0 * a_1 + c = out.
+            vec![s_mul_add * (a_0 * a_1 + c - out)]
+        });
+
+        let s_square_add_const = meta.selector();
+        meta.create_gate("square_add_const", |meta| {
+            //  +-----+---------+----------+--------------------+
+            //  | a_0 | a_1     | constant | s_square_add_const |
+            //  +-----+---------+----------+--------------------+
+            //  | a   | a^2 + c | c        |         1          |
+            //  +-----+---------+----------+--------------------+
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let out = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_fixed(constant);
+            let s_square_add_const = meta.query_selector(s_square_add_const);
+
+            // if s_square_add_const != 0, this constrains a^2 + c = out.
+            vec![s_square_add_const * (a.clone() * a + c - out)]
+        });
+
+        let s_select = meta.selector();
+        meta.create_gate("select", |meta| {
+            //  +------+-----+----------+
+            //  | a_0  | a_1 | s_select |
+            //  +------+-----+----------+
+            //  | cond | a   |    1     |
+            //  | out  | b   |    0     |
+            //  +------+-----+----------+
+            let cond = meta.query_advice(advice[0], Rotation::cur());
+            let a = meta.query_advice(advice[1], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::next());
+            let out = meta.query_advice(advice[0], Rotation::next());
+            let s_select = meta.query_selector(s_select);
+
+            let one = Expression::Constant(Fp::one());
+
+            // if s_select != 0, this constrains cond to be 0 or 1
+            // and out = a if cond = 1, out = b if cond = 0.
+            vec![
+                s_select.clone() * (cond.clone() * (one - cond.clone())),
+                s_select * (cond * (a - b.clone()) + b - out),
+            ]
+        });
+
+        let s_range = meta.selector();
+        meta.create_gate("range", |meta| {
+            //  bits are decomposed most significant first
+            //  +-------+---------------------+---------+
+            //  | a_0   | a_1                 | s_range |
+            //  +-------+---------------------+---------+
+            //  |       | 0                   |    1    |
+            //  | b_n-1 | b_n-1               |    1    |
+            //  | ...   | ...                 |    1    |
+            //  | b_0   | 2 * acc + b_0 = a   |    0    |
+            //  +-------+---------------------+---------+
+            let acc = meta.query_advice(advice[1], Rotation::cur());
+            let bit = meta.query_advice(advice[0], Rotation::next());
+            let acc_next = meta.query_advice(advice[1], Rotation::next());
+            let s_range = meta.query_selector(s_range);
+
+            let one = Expression::Constant(Fp::one());
+            let two = Expression::Constant(Fp::from(2));
+
+            // if s_range != 0, this constrains the next bit to be 0 or 1
+            // and accumulates it into acc_next = 2 * acc + bit.
+            vec![
+                s_range.clone() * (bit.clone() * (one - bit.clone())),
+                s_range * (two * acc + bit - acc_next),
+            ]
+        });
+
+        FieldConfig {
+            advice,
+            instance,
+            constant,
+            s_mul,
+            s_add,
+            s_sub,
+            s_mul_add,
+            s_square_add_const,
+            s_select,
+            s_range,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Number(pub AssignedCell<Fp, Fp>);
+
+/// The generators of the toy commitment checked by [`FieldChip::verify_commitment`].
+/// They are field elements rather than curve points, so the commitment is
+/// neither hiding nor binding: anyone can solve for a blinding that opens it
+/// to any value.
+pub const COMMITMENT_G: Fp = Fp::from_raw([7, 0, 0, 0]);
+pub const COMMITMENT_H: Fp = Fp::from_raw([11, 0, 0, 0]);
+
+/// Computes the toy commitment `g * value + h * blinding` outside of a circuit.
+pub fn commit(value: Fp, blinding: Fp) -> Fp {
+    COMMITMENT_G * value + COMMITMENT_H * blinding
+}
+
+impl FieldChip {
+    /// Evaluates `coeffs[0] + coeffs[1] * x + ... + coeffs[n] * x^n` with Horner's method.
+    pub fn eval_horner(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: Number,
+        coeffs: &[Fp],
+    ) -> Result<Number, Error> {
+        let coeffs = self.load_constants(layouter.namespace(|| "coeffs"), coeffs)?;
+        let (leading, rest) = coeffs.split_last().ok_or(Error::Synthesis)?;
+
+        let mut acc = leading.clone();
+        for c in rest.iter().rev() {
+            let acc_x = self.mul(layouter.namespace(|| "acc * x"), acc, x.clone())?;
+            acc = self.add(layouter.namespace(|| "acc * x + c"), acc_x, c.clone())?;
+        }
+
+        Ok(acc)
+    }
+
+    /// Evaluates the polynomial like [`Self::eval_horner`] for coefficients that
+    /// are already assigned, with one `mul_add` per step.
+    pub fn eval_horner_assigned(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: Number,
+        coeffs: &[Number],
+    ) -> Result<Number, Error> {
+        let (leading, rest) = coeffs.split_last().ok_or(Error::Synthesis)?;
+
+        let mut acc = leading.clone();
+        for c in rest.iter().rev() {
+            acc = self.mul_add(
+                layouter.namespace(|| "acc * x + c"),
+                acc,
+                x.clone(),
+                c.clone(),
+            )?;
+        }
+
+        Ok(acc)
+    }
+
+    /// Evaluates the polynomial like [`Self::eval_horner`], but computes the powers
+    /// `x, x^2, ..., x^n` first and then sums `coeffs[i] * x^i` with `mul_add`.
+    /// That is `2n - 1` regions against `2n` for Horner's method, so the gain on
+    /// its own is small; the powers could also be shared between polynomials.
+    pub fn eval_precomputed_powers(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: Number,
+        coeffs: &[Fp],
+    ) -> Result<Number, Error> {
+        let coeffs = self.load_constants(layouter.namespace(|| "coeffs"), coeffs)?;
+        let (constant, rest) = coeffs.split_first().ok_or(Error::Synthesis)?;
+
+        let mut powers = Vec::with_capacity(rest.len());
+        if !rest.is_empty() {
+            powers.push(x.clone());
+        }
+        for _ in 1..rest.len() {
+            let last = powers.last().cloned().unwrap();
+            powers.push(self.mul(layouter.namespace(|| "x^i"), last, x.clone())?);
+        }
+
+        let mut acc = constant.clone();
+        for (c, power) in rest.iter().zip(powers) {
+            acc = self.mul_add(
+                layouter.namespace(|| "acc + c * x^i"),
+                power,
+                c.clone(),
+                acc,
+            )?;
+        }
+
+        Ok(acc)
+    }
+
+    /// Exposes `num` as 32 big-endian bytes at instance[start_row..start_row + 32].
+    /// Each byte is range checked and the bytes must recompose to `num`. The
+    /// recomposition is modulo the field, so a verifier that needs the canonical
+    /// encoding should also check that the bytes are below the modulus.
+    pub fn expose_bytes(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        num: Number,
+        start_row: usize,
+    ) -> Result<(), Error> {
+        let repr = num.0.value().map(|num| num.to_repr());
+        let base = self.load_constant(layouter.namespace(|| "256"), Fp::from(256))?;
+
+        let mut acc = self.load_constant(layouter.namespace(|| "zero"), Fp::zero())?;
+        for i in 0..32 {
+            // the repr is little-endian
+            let byte = repr.map(|repr| Fp::from(repr[31 - i] as u64));
+            let byte = self.witness(layouter.namespace(|| format!("byte {}", i)), || byte)?;
+            self.range_check(layouter.namespace(|| "byte"), byte.clone(), 8)?;
+
+            acc = self.mul_add(
+                layouter.namespace(|| "acc * 256 + byte"),
+                acc,
+                base.clone(),
+                byte.clone(),
+            )?;
+            self.expose_public(layouter.namespace(|| "expose byte"), byte, start_row + i)?;
+        }
+
+        self.assert_equal(layouter.namespace(|| "bytes == num"), acc, num)
+    }
+
+    /// Evaluates `coeffs_a` at x if `x < t`, and `coeffs_b` otherwise. Both
+    /// polynomials are evaluated, and the comparison picks one with `select`.
+    /// x and t must fit in a u64.
+    pub fn eval_piecewise(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: Number,
+        t: Fp,
+        coeffs_a: &[Fp],
+        coeffs_b: &[Fp],
+    ) -> Result<Number, Error> {
+        let t_int = to_u64(&t).ok_or(Error::Synthesis)?;
+        self.range_check(layouter.namespace(|| "x"), x.clone(), 64)?;
+
+        let below = x.0.value().map(|x| {
+            let below = to_u64(x).is_some_and(|x| x < t_int);
+            Fp::from(below as u64)
+        });
+        let below = self.witness(layouter.namespace(|| "x < t"), || below)?;
+
+        // d = t - 1 - x if x < t and d = x - t otherwise. The wrong choice makes
+        // d negative, which fails the range check. select constrains below to
+        // be a bit.
+        let t_minus_one = self.load_constant(layouter.namespace(|| "t - 1"), t - Fp::one())?;
+        let t = self.load_constant(layouter.namespace(|| "t"), t)?;
+        let if_below = self.sub(layouter.namespace(|| "t - 1 - x"), t_minus_one, x.clone())?;
+        let if_above = self.sub(layouter.namespace(|| "x - t"), x.clone(), t)?;
+        let delta = self.sub(
+            layouter.namespace(|| "if_below - if_above"),
+            if_below,
+            if_above.clone(),
+        )?;
+        let d = self.mul_add(layouter.namespace(|| "d"), below.clone(), delta, if_above)?;
+        self.range_check(layouter.namespace(|| "d"), d, 64)?;
+
+        let y_a = self.eval_horner(layouter.namespace(|| "eval a"), x.clone(), coeffs_a)?;
+        let y_b = self.eval_horner(layouter.namespace(|| "eval b"), x, coeffs_b)?;
+
+        self.select(layouter.namespace(|| "select"), below, y_a, y_b)
+    }
+
+    /// Computes `weights[0] * nums[0] + ... + weights[n] * nums[n]`. The weights
+    /// are circuit constants, copied from the fixed column into the `mul_add`
+    /// regions of [`NumbericInstructions::dot`].
+    pub fn weighted_sum(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        nums: &[Number],
+        weights: &[Fp],
+    ) -> Result<Number, Error> {
+        let weights = self.load_constants(layouter.namespace(|| "weights"), weights)?;
+        self.dot(layouter.namespace(|| "weighted sum"), nums, &weights)
+    }
+
+    /// Evaluates the polynomial like [`Self::eval_horner`] and also returns the
+    /// square of the result.
+    pub fn eval_and_square(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: Number,
+        coeffs: &[Fp],
+    ) -> Result<(Number, Number), Error> {
+        let y = self.eval_horner(layouter.namespace(|| "eval"), x, coeffs)?;
+        let y2 = self.mul(layouter.namespace(|| "y^2"), y.clone(), y.clone())?;
+
+        Ok((y, y2))
+    }
+
+    /// Evaluates the antiderivative without a constant term, that is
+    /// `coeffs[0] * x + coeffs[1] / 2 * x^2 + ... + coeffs[n] / (n + 1) * x^(n + 1)`.
+    /// The coefficients are circuit constants, so the divisions by `i + 1` are
+    /// field inversions done at synthesis, and Horner's method runs on the
+    /// rational coefficients.
+    pub fn eval_antiderivative(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: Number,
+        coeffs: &[Fp],
+    ) -> Result<Number, Error> {
+        // i + 1 < p, so the inverses always exist
+        let integrated: Vec<Fp> = std::iter::once(Fp::zero())
+            .chain(
+                coeffs
+                    .iter()
+                    .zip(1u64..)
+                    .map(|(c, i)| *c * Fp::from(i).invert().unwrap()),
+            )
+            .collect();
+
+        self.eval_horner(layouter.namespace(|| "eval"), x, &integrated)
+    }
+
+    /// Evaluates at `at` the polynomial of lowest degree through the points
+    /// `(xs[i], ys[i])`, as `sum(ys[j] * prod((at - xs[m]) / (xs[j] - xs[m])))`
+    /// over `m != j`. The points are circuit constants, so the denominators are
+    /// inverted at synthesis; the `xs` must be distinct.
+    pub fn eval_lagrange(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        xs: &[Fp],
+        ys: &[Fp],
+        at: Number,
+    ) -> Result<Number, Error> {
+        if xs.is_empty() || xs.len() != ys.len() {
+            return Err(Error::Synthesis);
+        }
+
+        // ys[j] / prod(xs[j] - xs[m]), zero has no inverse if two xs are equal
+        let weights = xs
+            .iter()
+            .zip(ys)
+            .enumerate()
+            .map(|(j, (x_j, y_j))| {
+                let denom = xs
+                    .iter()
+                    .enumerate()
+                    .filter(|(m, _)| *m != j)
+                    .fold(Fp::one(), |acc, (_, x_m)| acc * (*x_j - x_m));
+                Option::<Fp>::from(denom.invert())
+                    .map(|inv| *y_j * inv)
+                    .ok_or(Error::Synthesis)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let xs = self.load_constants(layouter.namespace(|| "xs"), xs)?;
+        let diffs = xs
+            .into_iter()
+            .map(|x_m| self.sub(layouter.namespace(|| "at - x_m"), at.clone(), x_m))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut basis = Vec::with_capacity(diffs.len());
+        for j in 0..diffs.len() {
+            let mut others = diffs
+                .iter()
+                .enumerate()
+                .filter(|(m, _)| *m != j)
+                .map(|(_, diff)| diff.clone());
+            let mut prod = match others.next() {
+                Some(diff) => diff,
+                // a single point: the polynomial is the constant ys[0]
+                None => self.load_constant(layouter.namespace(|| "one"), Fp::one())?,
+            };
+            for diff in others {
+                prod = self.mul(layouter.namespace(|| "prod(at - x_m)"), prod, diff)?;
+            }
+            basis.push(prod);
+        }
+
+        self.weighted_sum(layouter.namespace(|| "sum"), &basis, &weights)
+    }
+
+    /// Computes `a^2 - b^2` as `(a + b) * (a - b)`.
+    pub fn diff_of_squares(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Number,
+        b: Number,
+    ) -> Result<Number, Error> {
+        let sum = self.add(layouter.namespace(|| "a + b"), a.clone(), b.clone())?;
+        let diff = self.sub(layouter.namespace(|| "a - b"), a, b)?;
+
+        self.mul(layouter.namespace(|| "(a + b) * (a - b)"), sum, diff)
+    }
+
+    /// Reduces `a` modulo `p`, returning `r` with `a = q * p + r` and `r < p`.
+    /// Both `a` and `p` must fit in a u64.
+    pub fn reduce_mod(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Number,
+        p: Fp,
+    ) -> Result<Number, Error> {
+        let p_int = to_u64(&p).filter(|p| *p > 0).ok_or(Error::Synthesis)?;
+        let bits = (u64::BITS - (p_int - 1).leading_zeros()) as usize;
+
+        // an a that does not fit in a u64 gets q = r = 0, which fails the constraint below
+        let qr = a.0.value().map(|a| {
+            to_u64(a)
+                .map(|a| (a / p_int, a % p_int))
+                .unwrap_or_default()
+        });
+        let q = self.witness(layouter.namespace(|| "q"), || qr.map(|(q, _)| Fp::from(q)))?;
+        let r = self.witness(layouter.namespace(|| "r"), || qr.map(|(_, r)| Fp::from(r)))?;
+
+        // r < p as r and p - 1 - r both fit in the bits of p - 1. With q below
+        // 2^64, q * p + r cannot wrap around the field.
+        let p_minus_one = self.load_constant(layouter.namespace(|| "p - 1"), p - Fp::one())?;
+        let slack = self.sub(layouter.namespace(|| "p - 1 - r"), p_minus_one, r.clone())?;
+        self.range_check(layouter.namespace(|| "r"), r.clone(), bits)?;
+        self.range_check(layouter.namespace(|| "p - 1 - r"), slack, bits)?;
+        self.range_check(layouter.namespace(|| "q"), q.clone(), 64)?;
+
+        let p = self.load_constant(layouter.namespace(|| "p"), p)?;
+        let qpr = self.mul_add(layouter.namespace(|| "q * p + r"), q, p, r.clone())?;
+        self.assert_equal(layouter.namespace(|| "a == q * p + r"), a, qpr)?;
+
+        Ok(r)
+    }
+
+    /// Computes `base^exp mod modulus` by square-and-multiply over the bits of
+    /// the public `exp`, reducing with [`Self::reduce_mod`] after every product.
+    /// The reduced values stay below `modulus`, so their products fit in a u64
+    /// as long as `modulus <= 2^32`; larger moduli fail with [`Error::Synthesis`].
+    pub fn mod_pow(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        base: Number,
+        exp: u64,
+        modulus: Fp,
+    ) -> Result<Number, Error> {
+        if to_u64(&modulus).is_none_or(|m| m > 1 << 32) {
+            return Err(Error::Synthesis);
+        }
+
+        let base = self.reduce_mod(layouter.namespace(|| "base mod m"), base, modulus)?;
+        if exp == 0 {
+            let one = self.load_constant(layouter.namespace(|| "one"), Fp::one())?;
+            return self.reduce_mod(layouter.namespace(|| "1 mod m"), one, modulus);
+        }
+
+        // the leading bit of exp sets acc = base
+        let mut acc = base.clone();
+        for bit in (0..exp.ilog2()).rev() {
+            let square = self.mul(layouter.namespace(|| "acc^2"), acc.clone(), acc)?;
+            acc = self.reduce_mod(layouter.namespace(|| "acc^2 mod m"), square, modulus)?;
+            if exp >> bit & 1 == 1 {
+                let product = self.mul(layouter.namespace(|| "acc * base"), acc, base.clone())?;
+                acc =
+                    self.reduce_mod(layouter.namespace(|| "acc * base mod m"), product, modulus)?;
+            }
+        }
+
+        Ok(acc)
+    }
+
+    /// Constrains `g * value + h * blinding` to equal the public `commitment`,
+    /// a field version of a Pedersen opening with [`COMMITMENT_G`] and
+    /// [`COMMITMENT_H`] in place of fixed-base scalar multiplications.
+    pub fn verify_commitment(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        value: Number,
+        blinding: Number,
+        commitment: Fp,
+    ) -> Result<(), Error> {
+        let opened = self.weighted_sum(
+            layouter.namespace(|| "g * value + h * blinding"),
+            &[value, blinding],
+            &[COMMITMENT_G, COMMITMENT_H],
+        )?;
+        let commitment = self.load_constant(layouter.namespace(|| "commitment"), commitment)?;
+
+        self.assert_equal(
+            layouter.namespace(|| "opening == commitment"),
+            opened,
+            commitment,
+        )
+    }
+
+    /// Computes `base^exp` for a private `exp` of at most `bits` bits, by
+    /// square-and-multiply over the witnessed bits of `exp`: each step squares
+    /// the accumulator and selects whether to multiply it by `base`. The bits
+    /// are booleans by the select gate and must recompose to `exp`, which also
+    /// range checks it. `bits` is at most 64.
+    pub fn pow_private(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        base: Fp,
+        exp: Number,
+        bits: usize,
+    ) -> Result<Number, Error> {
+        if bits == 0 || bits > 64 {
+            return Err(Error::Synthesis);
+        }
+
+        // an exp that does not fit in a u64 gets all zero bits, which fails
+        // the recomposition below
+        let exp_int = exp.0.value().map(|exp| to_u64(exp).unwrap_or_default());
+        let base = self.load_constant(layouter.namespace(|| "base"), base)?;
+        let two = self.load_constant(layouter.namespace(|| "two"), Fp::from(2))?;
+        let mut acc = self.load_constant(layouter.namespace(|| "one"), Fp::one())?;
+        let mut recomposed = self.load_constant(layouter.namespace(|| "zero"), Fp::zero())?;
+
+        for i in (0..bits).rev() {
+            let bit = self.witness(layouter.namespace(|| format!("bit {}", i)), || {
+                exp_int.map(|exp| Fp::from(exp >> i & 1))
+            })?;
+            recomposed = self.mul_add(
+                layouter.namespace(|| "recomposed * 2 + bit"),
+                recomposed,
+                two.clone(),
+                bit.clone(),
+            )?;
+
+            let square = self.mul(layouter.namespace(|| "acc^2"), acc.clone(), acc)?;
+            let times_base = self.mul(
+                layouter.namespace(|| "acc^2 * base"),
+                square.clone(),
+                base.clone(),
+            )?;
+            acc = self.select(
+                layouter.namespace(|| "bit ? acc^2 * base : acc^2"),
+                bit,
+                times_base,
+                square,
+            )?;
+        }
+        self.assert_equal(layouter.namespace(|| "bits == exp"), recomposed, exp)?;
+
+        Ok(acc)
+    }
+
+    /// Proves that `a` and `b` are coprime integers with a Bezout witness
+    /// `a * u + b * v = 1`. Over the field alone the identity says nothing, as
+    /// `u = 1 / a, v = 0` works for any nonzero `a`. So `a` and `b` are range
+    /// checked to 64 bits and the signed `u` and `v` to `[-2^63, 2^63)`; then
+    /// `|a * u + b * v| < 2^128` cannot wrap around the modulus, and the
+    /// field identity holds over the integers.
+    pub fn coprime_witness(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Number,
+        b: Number,
+        u: Number,
+        v: Number,
+    ) -> Result<(), Error> {
+        self.range_check(layouter.namespace(|| "a < 2^64"), a.clone(), 64)?;
+        self.range_check(layouter.namespace(|| "b < 2^64"), b.clone(), 64)?;
+
+        let offset = self.load_constant(layouter.namespace(|| "2^63"), Fp::from(1 << 63))?;
+        for (name, coeff) in [("u", &u), ("v", &v)] {
+            let shifted = self.add(
+                layouter.namespace(|| format!("{} + 2^63", name)),
+                coeff.clone(),
+                offset.clone(),
+            )?;
+            self.range_check(
+                layouter.namespace(|| format!("{} + 2^63 < 2^64", name)),
+                shifted,
+                64,
+            )?;
+        }
+
+        let bv = self.mul(layouter.namespace(|| "b * v"), b, v)?;
+        let sum = self.mul_add(layouter.namespace(|| "a * u + b * v"), a, u, bv)?;
+        let one = self.load_constant(layouter.namespace(|| "one"), Fp::one())?;
+        self.assert_equal(layouter.namespace(|| "a * u + b * v == 1"), sum, one)
+    }
+
+    /// Constrains `a` to be nonzero by witnessing its inverse, as `a * a_inv = 1`
+    /// has no solution for `a = 0`. Used to guard the denominator of a division.
+    pub fn assert_nonzero(&self, mut layouter: impl Layouter<Fp>, a: Number) -> Result<(), Error> {
+        // zero has no inverse, the prover then witnesses 0 and the check fails
+        let a_inv = a.0.value().map(|a| a.invert().unwrap_or(Fp::zero()));
+        let a_inv = self.witness(layouter.namespace(|| "a_inv"), || a_inv)?;
+
+        let product = self.mul(layouter.namespace(|| "a * a_inv"), a, a_inv)?;
+        let one = self.load_constant(layouter.namespace(|| "one"), Fp::one())?;
+        self.assert_equal(layouter.namespace(|| "a * a_inv == 1"), product, one)
+    }
+}
+
+impl NumbericInstructions for FieldChip {
+    type Num = Number;
+
+    fn load_private(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: Value<Fp>,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "load private",
+            |mut region| {
+                region
+                    .assign_advice(|| "private input", config.advice[0], 0, || x)
+                    .map(Number)
+            },
+        )
+    }
+
+    fn load_private_many(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        xs: &[Value<Fp>],
+    ) -> Result<Vec<Self::Num>, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "load private many",
+            |mut region| {
+                // fill both advice columns row by row
+                xs.iter()
+                    .enumerate()
+                    .map(|(i, x)| {
+                        region
+                            .assign_advice(
+                                || format!("private input {}", i),
+                                config.advice[i % 2],
+                                i / 2,
+                                || *x,
+                            )
+                            .map(Number)
+                    })
+                    .collect()
+            },
+        )
+    }
+
+    fn load_constant(&self, mut layouter: impl Layouter<Fp>, x: Fp) -> Result<Self::Num, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "load constant",
+            |mut region| {
+                region
+                    .assign_advice_from_constant(|| "constant value", config.advice[0], 0, x)
+                    .map(Number)
+            },
+        )
+    }
+
+    fn load_constants(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        xs: &[Fp],
+    ) -> Result<Vec<Self::Num>, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "load constants",
+            |mut region| {
+                // same packing as load_private_many
+                xs.iter()
+                    .enumerate()
+                    .map(|(i, x)| {
+                        region
+                            .assign_advice_from_constant(
+                                || format!("constant {}", i),
+                                config.advice[i % 2],
+                                i / 2,
+                                *x,
+                            )
+                            .map(Number)
+                    })
+                    .collect()
+            },
+        )
+    }
+
+    fn load_instance(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        row: usize,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "load instance",
+            |mut region| {
+                // copies instance[row] into the advice cell and constrains them to be equal
+                region
+                    .assign_advice_from_instance(
+                        || "public input",
+                        config.instance,
+                        row,
+                        config.advice[0],
+                        0,
+                    )
+                    .map(Number)
+            },
+        )
+    }
+
+    fn witness(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        f: impl FnOnce() -> Value<Fp>,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+        // the region closure may run more than once, so evaluate f up front
+        let value = f();
+
+        layouter.assign_region(
+            || "witness",
+            |mut region| {
+                region
+                    .assign_advice(|| "witness", config.advice[0], 0, || value)
+                    .map(Number)
+            },
+        )
+    }
+
+    fn add(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || "add",
+            |mut region| {
+                config.s_add.enable(&mut region, 0)?;
+                a.0.copy_advice(|| "s_0", &mut region, config.advice[0], 0)?;
+                b.0.copy_advice(|| "s_1", &mut region, config.advice[1], 0)?;
+
+                let value = a.0.value().copied() + b.0.value().copied();
+
+                region
+                    .assign_advice(|| "s_0 + s_1", config.advice[0], 1, || value)
+                    .map(Number)
+            },
+        )
+    }
+
+    fn sub(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || "sub",
+            |mut region| {
+                config.s_sub.enable(&mut region, 0)?;
+                a.0.copy_advice(|| "s_0", &mut region, config.advice[0], 0)?;
+                b.0.copy_advice(|| "s_1", &mut region, config.advice[1], 0)?;
+
+                let value = a.0.value().copied() - b.0.value().copied();
+
+                region
+                    .assign_advice(|| "s_0 - s_1", config.advice[0], 1, || value)
+                    .map(Number)
+            },
+        )
+    }
+
+    fn neg(&self, mut layouter: impl Layouter<Fp>, a: Self::Num) -> Result<Self::Num, Error> {
+        let zero = self.load_constant(layouter.namespace(|| "zero"), Fp::zero())?;
+        self.sub(layouter.namespace(|| "0 - a"), zero, a)
+    }
+
+    fn mul(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || "mul",
+            |mut region| {
+                config.s_mul.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "s_0", &mut region, config.advice[0], 0)?;
+                b.0.copy_advice(|| "s_1", &mut region, config.advice[1], 0)?;
+
+                let value = a.0.value().copied() * b.0.value();
+                region
+                    .assign_advice(|| "s_0 * s_1", config.advice[0], 1, || value)
+                    .map(Number)
+            },
+        )
+    }
+
+    fn mul_add(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+        c: Self::Num,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || "mul_add",
+            |mut region| {
+                config.s_mul_add.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, config.advice[1], 0)?;
+                c.0.copy_advice(|| "c", &mut region, config.advice[1], 1)?;
+
+                let value = a.0.value().copied() * b.0.value() + c.0.value();
+                region
+                    .assign_advice(|| "a * b + c", config.advice[0], 1, || value)
+                    .map(Number)
+            },
+        )
+    }
+
+    fn square_add_const(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        c: Fp,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || "square_add_const",
+            |mut region| {
+                config.s_square_add_const.enable(&mut region, 0)?;
+
+                a.0.copy_advice(|| "a", &mut region, config.advice[0], 0)?;
+                // the addend is fixed by the circuit, so it is not a witness
+                region.assign_fixed(|| "c", config.constant, 0, || Value::known(c))?;
+
+                let value = a.0.value().map(|a| a.square() + c);
+                region
+                    .assign_advice(|| "a^2 + c", config.advice[1], 0, || value)
+                    .map(Number)
+            },
+        )
+    }
+
+    fn select(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        cond: Self::Num,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<Self::Num, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || "select",
+            |mut region| {
+                config.s_select.enable(&mut region, 0)?;
+
+                cond.0
+                    .copy_advice(|| "cond", &mut region, config.advice[0], 0)?;
+                a.0.copy_advice(|| "a", &mut region, config.advice[1], 0)?;
+                b.0.copy_advice(|| "b", &mut region, config.advice[1], 1)?;
+
+                let value = cond
+                    .0
+                    .value()
+                    .zip(a.0.value().zip(b.0.value()))
+                    .map(|(cond, (a, b))| if *cond == Fp::one() { *a } else { *b });
+                region
+                    .assign_advice(|| "cond ? a : b", config.advice[0], 1, || value)
+                    .map(Number)
+            },
+        )
+    }
+
+    fn range_check(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        bits: usize,
+    ) -> Result<(), Error> {
+        if bits > 64 {
+            return Err(Error::Synthesis);
+        }
+        let config = self.config();
+        // a value that does not fit in a u64 decomposes into garbage, which the
+        // copy constraint on the accumulator rejects
+        let value = a.0.value().map(|a| to_u64(a).unwrap_or_default());
+
+        layouter.assign_region(
+            || "range check",
+            |mut region| {
+                let mut acc = region.assign_advice_from_constant(
+                    || "acc",
+                    config.advice[1],
+                    0,
+                    Fp::zero(),
+                )?;
+
+                for i in 0..bits {
+                    config.s_range.enable(&mut region, i)?;
+
+                    let bit = value.map(|a| (a >> (bits - 1 - i)) & 1);
+                    region.assign_advice(
+                        || format!("bit {}", bits - 1 - i),
+                        config.advice[0],
+                        i + 1,
+                        || bit.map(Fp::from),
+                    )?;
+
+                    let next = acc.value().map(|acc| acc.double()) + bit.map(Fp::from);
+                    acc = region.assign_advice(|| "acc", config.advice[1], i + 1, || next)?;
+                }
+
+                region.constrain_equal(a.0.cell(), acc.cell())
+            },
+        )
+    }
+
+    fn dot(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: &[Self::Num],
+        b: &[Self::Num],
+    ) -> Result<Self::Num, Error> {
+        if a.len() != b.len() {
+            return Err(Error::Synthesis);
+        }
+
+        let mut acc = self.load_constant(layouter.namespace(|| "zero"), Fp::zero())?;
+        for (i, (a_i, b_i)) in a.iter().zip(b).enumerate() {
+            acc = self.mul_add(
+                layouter.namespace(|| format!("a_{} * b_{} + acc", i, i)),
+                a_i.clone(),
+                b_i.clone(),
+                acc,
+            )?;
+        }
+
+        Ok(acc)
+    }
+
+    fn assert_equal(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: Self::Num,
+        b: Self::Num,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "assert equal",
+            // a copy constraint between the two cells, no gate needed
+            |mut region| region.constrain_equal(a.0.cell(), b.0.cell()),
+        )
+    }
+
+    fn expose_public(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        num: Self::Num,
+        row: usize,
+    ) -> Result<(), Error> {
+        let config = self.config();
+        layouter.constrain_instance(num.0.cell(), config.instance, row)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MyCircuit {
+    pub constant: Fp,
+    pub x: Value<Fp>,
+}
+
+impl Circuit<Fp> for MyCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        let advice = [meta.advice_column(), meta.advice_column()];
+        let instance = meta.instance_column();
+        let constant = meta.fixed_column();
+
+        FieldChip::configure(meta, advice, instance, constant)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        // let x = self.x;
+        let chip = FieldChip::new(config);
+
+        let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+        let constant = chip.load_constant(layouter.namespace(|| "load constant"), self.constant)?;
+
+        let x2 = chip.mul(layouter.namespace(|| "x^2"), x.clone(), x.clone())?;
+        let x3 = chip.mul(layouter.namespace(|| "x^3"), x2, x.clone())?;
+        let x3_plus_x = chip.add(layouter.namespace(|| "x^3 + x"), x3, x)?;
+        let x3_plus_x_plus_5 =
+            chip.add(layouter.namespace(|| "x^3 + x + 5"), x3_plus_x, constant)?;
+
+        chip.expose_public(layouter.namespace(|| "expose res"), x3_plus_x_plus_5, 0)
+    }
+}
+
+/// Builds [`MyCircuit`] and [`PolynomialCircuit`](polynomial::PolynomialCircuit)
+/// from plain integers. Unset witnesses stay unknown, as for key generation.
+#[derive(Clone, Debug, Default)]
+pub struct CircuitBuilder {
+    x: Option<u64>,
+    constant: u64,
+    coeffs: Vec<u64>,
+    pad_to_k: Option<u32>,
+}
+
+impl CircuitBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn x(mut self, x: u64) -> Self {
+        self.x = Some(x);
+        self
+    }
+
+    pub fn constant(mut self, constant: u64) -> Self {
+        self.constant = constant;
+        self
+    }
+
+    /// Coefficients of the polynomial, lowest degree first.
+    pub fn coeffs(mut self, coeffs: &[u64]) -> Self {
+        self.coeffs = coeffs.to_vec();
+        self
+    }
+
+    /// Pads the built polynomial circuit to `2^k` rows, see
+    /// [`PolynomialCircuit::pad_to_k`](polynomial::PolynomialCircuit::pad_to_k).
+    pub fn pad_to_k(mut self, k: u32) -> Self {
+        self.pad_to_k = Some(k);
+        self
+    }
+
+    fn x_value(&self) -> Value<Fp> {
+        self.x
+            .map_or(Value::unknown(), |x| Value::known(Fp::from(x)))
+    }
+
+    /// Returns `MyCircuit` for `x^3 + x + constant`. The coefficients and the
+    /// padding are not used.
+    pub fn build(&self) -> MyCircuit {
+        MyCircuit {
+            constant: Fp::from(self.constant),
+            x: self.x_value(),
+        }
+    }
+
+    /// Returns a `PolynomialCircuit` for the coefficients. The constant is not used.
+    pub fn build_polynomial(&self) -> polynomial::PolynomialCircuit {
+        polynomial::PolynomialCircuit {
+            coeffs: self.coeffs.iter().map(|c| Fp::from(*c)).collect(),
+            x: self.x_value(),
+            pad_to_k: self.pad_to_k,
+        }
+    }
+}
+
+// Returns the field element as an integer if it fits in a u64
+fn to_u64(value: &Fp) -> Option<u64> {
+    let repr = value.to_repr();
+    let (low, high) = repr.split_at(8);
+    if high.iter().any(|byte| *byte != 0) {
+        return None;
+    }
+
+    Some(u64::from_le_bytes(low.try_into().unwrap()))
+}
+
+// Reads the number following `label` in a debug or display dump. The
+// constraint system keeps its counts private, but its pinned form prints them.
+fn dump_count(dump: &str, label: &str) -> Option<usize> {
+    let start = dump.find(label)? + label.len();
+    dump[start..]
+        .trim_start()
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+// Returns how many rows of `2^k` the circuit can assign, the ones after them
+// hold blinding factors.
+pub(crate) fn usable_rows<C: Circuit<Fp>>(k: u32) -> Result<usize, Error> {
+    let mut meta = ConstraintSystem::<Fp>::default();
+    C::configure(&mut meta);
+    (1usize << k)
+        .checked_sub(meta.blinding_factors() + 1)
+        .ok_or(Error::NotEnoughRowsAvailable { current_k: k })
+}
+
+/// Checks that `MyCircuit` needs at most `max_advice` advice columns.
+pub fn check_columns(max_advice: usize) -> Result<(), String> {
+    let mut meta = ConstraintSystem::<Fp>::default();
+    MyCircuit::configure(&mut meta);
+
+    let pinned = format!("{:?}", meta.pinned());
+    let advice = dump_count(&pinned, "num_advice_columns:")
+        .ok_or("the constraint system does not report its advice columns")?;
+    if advice > max_advice {
+        return Err(format!(
+            "the circuit needs {} advice columns, but the budget is {}",
+            advice, max_advice
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::arithmetic::Field;
+    use halo2_proofs::dev::{CircuitCost, CircuitGates, MockProver};
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::SeedableRng;
+
+    // fixed so that a failing random case can be reproduced
+    const SEED: u64 = 0x5eed;
+
+    // y = x^3 + x + 5 where x is read from instance[0] and y is exposed at instance[1]
+    #[derive(Default)]
+    struct PublicInputCircuit {
+        constant: Fp,
+    }
+
+    impl Circuit<Fp> for PublicInputCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let x = chip.load_instance(layouter.namespace(|| "load x"), 0)?;
+            let constant =
+                chip.load_constant(layouter.namespace(|| "load constant"), self.constant)?;
+
+            let x2 = chip.mul(layouter.namespace(|| "x^2"), x.clone(), x.clone())?;
+            let x3 = chip.mul(layouter.namespace(|| "x^3"), x2, x.clone())?;
+            let x3_plus_x = chip.add(layouter.namespace(|| "x^3 + x"), x3, x)?;
+            let res = chip.add(layouter.namespace(|| "x^3 + x + 5"), x3_plus_x, constant)?;
+
+            chip.expose_public(layouter.namespace(|| "expose res"), res, 1)
+        }
+    }
+
+    // exposes neg(neg(a)) at instance[0] and a + neg(a) at instance[1]
+    struct NegCircuit {
+        a: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for NegCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                a: Value::unknown(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let a = chip.load_private(layouter.namespace(|| "load a"), self.a)?;
+            let neg_a = chip.neg(layouter.namespace(|| "-a"), a.clone())?;
+            let neg_neg_a = chip.neg(layouter.namespace(|| "-(-a)"), neg_a.clone())?;
+            let sum = chip.add(layouter.namespace(|| "a + (-a)"), a, neg_a)?;
+
+            chip.expose_public(layouter.namespace(|| "expose -(-a)"), neg_neg_a, 0)?;
+            chip.expose_public(layouter.namespace(|| "expose a + (-a)"), sum, 1)
+        }
+    }
+
+    #[test]
+    fn test_neg() {
+        let k = 4;
+        let a = Fp::from(7);
+        let circuit = NegCircuit { a: Value::known(a) };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![a, Fp::zero()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(k, &circuit, vec![vec![-a, Fp::zero()]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // exposes the dot product of two private vectors at instance[0]
+    struct DotCircuit {
+        a: Vec<Value<Fp>>,
+        b: Vec<Value<Fp>>,
+    }
+
+    impl Circuit<Fp> for DotCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                a: vec![Value::unknown(); self.a.len()],
+                b: vec![Value::unknown(); self.b.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let a = self
+                .a
+                .iter()
+                .map(|a| chip.load_private(layouter.namespace(|| "load a"), *a))
+                .collect::<Result<Vec<_>, _>>()?;
+            let b = self
+                .b
+                .iter()
+                .map(|b| chip.load_private(layouter.namespace(|| "load b"), *b))
+                .collect::<Result<Vec<_>, _>>()?;
+            let res = chip.dot(layouter.namespace(|| "a . b"), &a, &b)?;
+
+            chip.expose_public(layouter.namespace(|| "expose res"), res, 0)
+        }
+    }
+
+    // loads [a, b, c] in one region and exposes a * b + c at instance[0]
+    struct LoadManyCircuit {
+        xs: Vec<Value<Fp>>,
+    }
+
+    impl Circuit<Fp> for LoadManyCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                xs: vec![Value::unknown(); self.xs.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let xs = chip.load_private_many(layouter.namespace(|| "load xs"), &self.xs)?;
+            let res = chip.mul_add(
+                layouter.namespace(|| "a * b + c"),
+                xs[0].clone(),
+                xs[1].clone(),
+                xs[2].clone(),
+            )?;
+
+            chip.expose_public(layouter.namespace(|| "expose res"), res, 0)
+        }
+    }
+
+    #[test]
+    fn test_load_private_many() {
+        let k = 4;
+        let circuit = LoadManyCircuit {
+            xs: known(&[3, 5, 7]),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(22)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // the inputs must come back in order
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(26)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // y = x^3 + x + 5 with the coefficients loaded in one region
+    #[derive(Default)]
+    struct LoadConstantsCircuit {
+        x: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for LoadConstantsCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+            let coeffs = [5, 1, 0, 1].map(Fp::from);
+            let c = chip.load_constants(layouter.namespace(|| "load coeffs"), &coeffs)?;
+
+            let mut acc = c[3].clone();
+            for c_i in c[..3].iter().rev() {
+                acc = chip.mul_add(
+                    layouter.namespace(|| "acc * x + c"),
+                    acc,
+                    x.clone(),
+                    c_i.clone(),
+                )?;
+            }
+
+            chip.expose_public(layouter.namespace(|| "expose y"), acc, 0)
+        }
+    }
+
+    #[test]
+    fn test_load_constants() {
+        let k = 4;
+        let circuit = LoadConstantsCircuit {
+            x: Value::known(Fp::from(3)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(35)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(36)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn known(xs: &[u64]) -> Vec<Value<Fp>> {
+        xs.iter().map(|x| Value::known(Fp::from(*x))).collect()
+    }
+
+    #[test]
+    fn test_dot() {
+        let k = 5;
+        let circuit = DotCircuit {
+            a: known(&[1, 2, 3]),
+            b: known(&[4, 5, 6]),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(32)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(33)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        let circuit = DotCircuit {
+            a: known(&[1, 2, 3]),
+            b: known(&[4, 5]),
+        };
+        assert!(MockProver::run(k, &circuit, vec![vec![Fp::from(32)]]).is_err());
+    }
+
+    // feeds an injected x^2 into the mul gate as the output of x * x
+    struct WitnessCircuit {
+        x: Value<Fp>,
+        x2: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for WitnessCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                x: Value::unknown(),
+                x2: Value::unknown(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config.clone());
+
+            let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+            let x2 = chip.witness(layouter.namespace(|| "inject x^2"), || self.x2)?;
+
+            layouter.assign_region(
+                || "x * x",
+                |mut region| {
+                    config.s_mul.enable(&mut region, 0)?;
+                    x.0.copy_advice(|| "x", &mut region, config.advice[0], 0)?;
+                    x.0.copy_advice(|| "x", &mut region, config.advice[1], 0)?;
+                    x2.0.copy_advice(|| "x^2", &mut region, config.advice[0], 1)?;
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_witness() {
+        let k = 4;
+
+        let circuit = WitnessCircuit {
+            x: Value::known(Fp::from(3)),
+            x2: Value::known(Fp::from(9)),
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let circuit = WitnessCircuit {
+            x: Value::known(Fp::from(3)),
+            x2: Value::known(Fp::from(10)),
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // y = x^2 + c in a single row
+    #[derive(Default)]
+    struct SquareAddConstCircuit {
+        c: Fp,
+        x: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for SquareAddConstCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                c: self.c,
+                x: Value::unknown(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+            let y = chip.square_add_const(layouter.namespace(|| "x^2 + c"), x, self.c)?;
+
+            chip.expose_public(layouter.namespace(|| "expose y"), y, 0)
+        }
+    }
+
+    #[test]
+    fn test_square_add_const() {
+        let k = 4;
+        let circuit = SquareAddConstCircuit {
+            c: Fp::from(5),
+            x: Value::known(Fp::from(3)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(14)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(15)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // a^2 - b^2 by difference of squares, checked against a * a - b * b
+    #[derive(Default)]
+    struct DiffOfSquaresCircuit {
+        a: Value<Fp>,
+        b: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for DiffOfSquaresCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let a = chip.load_private(layouter.namespace(|| "load a"), self.a)?;
+            let b = chip.load_private(layouter.namespace(|| "load b"), self.b)?;
+
+            let fused =
+                chip.diff_of_squares(layouter.namespace(|| "fused"), a.clone(), b.clone())?;
+            let a2 = chip.mul(layouter.namespace(|| "a * a"), a.clone(), a)?;
+            let b2 = chip.mul(layouter.namespace(|| "b * b"), b.clone(), b)?;
+            let naive = chip.sub(layouter.namespace(|| "a * a - b * b"), a2, b2)?;
+
+            chip.assert_equal(
+                layouter.namespace(|| "fused == naive"),
+                fused.clone(),
+                naive,
+            )?;
+            chip.expose_public(layouter.namespace(|| "expose"), fused, 0)
+        }
+    }
+
+    #[test]
+    fn test_diff_of_squares() {
+        let k = 5;
+        let mut rng = ChaCha8Rng::seed_from_u64(SEED);
+
+        for _ in 0..10 {
+            let a = Fp::random(&mut rng);
+            let b = Fp::random(&mut rng);
+            let circuit = DiffOfSquaresCircuit {
+                a: Value::known(a),
+                b: Value::known(b),
+            };
+
+            let prover = MockProver::run(k, &circuit, vec![vec![a * a - b * b]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    // r = a mod p is exposed at instance[0]
+    #[derive(Default)]
+    struct ReduceModCircuit {
+        a: Value<Fp>,
+        p: Fp,
+    }
+
+    impl Circuit<Fp> for ReduceModCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                a: Value::unknown(),
+                p: self.p,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let a = chip.load_private(layouter.namespace(|| "load a"), self.a)?;
+            let r = chip.reduce_mod(layouter.namespace(|| "a mod p"), a, self.p)?;
+
+            chip.expose_public(layouter.namespace(|| "expose r"), r, 0)
+        }
+    }
+
+    #[test]
+    fn test_reduce_mod() {
+        let k = 8;
+        let circuit = ReduceModCircuit {
+            a: Value::known(Fp::from(1000)),
+            p: Fp::from(7),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(6)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // 1000 = 142 * 7 + 6, so 13 = 6 + 7 is congruent but not reduced
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(13)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // base^exp mod m at instance[0]
+    struct ModPowCircuit {
+        base: Value<Fp>,
+        exp: u64,
+        modulus: Fp,
+    }
+
+    impl Circuit<Fp> for ModPowCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                base: Value::unknown(),
+                ..*self
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let base = chip.load_private(layouter.namespace(|| "load base"), self.base)?;
+            let r = chip.mod_pow(
+                layouter.namespace(|| "base^exp mod m"),
+                base,
+                self.exp,
+                self.modulus,
+            )?;
+
+            chip.expose_public(layouter.namespace(|| "expose r"), r, 0)
+        }
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        let k = 10;
+        let circuit = |exp: u64| ModPowCircuit {
+            base: Value::known(Fp::from(3)),
+            exp,
+            modulus: Fp::from(5),
+        };
+
+        // 3^4 = 81 = 16 * 5 + 1
+        let prover = MockProver::run(k, &circuit(4), vec![vec![Fp::one()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        let prover = MockProver::run(k, &circuit(4), vec![vec![Fp::from(81)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // 3^5 = 243 = 48 * 5 + 3 and 3^0 = 1
+        let prover = MockProver::run(k, &circuit(5), vec![vec![Fp::from(3)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        let prover = MockProver::run(k, &circuit(0), vec![vec![Fp::one()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    // y = f(x) at instance[0], with either evaluation path
+    #[derive(Debug)]
+    struct PowersCircuit {
+        coeffs: Vec<Fp>,
+        x: Value<Fp>,
+        precomputed: bool,
+    }
+
+    impl Circuit<Fp> for PowersCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                coeffs: self.coeffs.clone(),
+                x: Value::unknown(),
+                precomputed: self.precomputed,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+            let y = if self.precomputed {
+                chip.eval_precomputed_powers(layouter.namespace(|| "eval"), x, &self.coeffs)?
+            } else {
+                chip.eval_horner(layouter.namespace(|| "eval"), x, &self.coeffs)?
+            };
+
+            chip.expose_public(layouter.namespace(|| "expose y"), y, 0)
+        }
+    }
+
+    #[test]
+    fn test_eval_precomputed_powers() {
+        let k = 7;
+        let x = Fp::from(3);
+        // degree 8
+        let coeffs = [5, 1, 0, 1, 2, 0, 0, 7, 1].map(Fp::from).to_vec();
+        let y = polynomial::eval_generic(&coeffs, x);
+
+        let advice_rows = |precomputed: bool| {
+            let circuit = PowersCircuit {
+                coeffs: coeffs.clone(),
+                x: Value::known(x),
+                precomputed,
+            };
+            let prover = MockProver::run(k, &circuit, vec![vec![y]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+            let wrong = MockProver::run(k, &circuit, vec![vec![y + Fp::one()]]).unwrap();
+            assert!(wrong.verify().is_err());
+
+            let cost = format!(
+                "{:?}",
+                CircuitCost::<halo2_proofs::pasta::Eq, _>::measure(k, &circuit)
+            );
+            count(&cost, "max_advice_rows:")
+        };
+
+        let horner = advice_rows(false);
+        let precomputed = advice_rows(true);
+        assert!(precomputed < horner);
+    }
+
+    // y = a(x) if x < t else b(x), exposed at instance[0]
+    struct PiecewiseCircuit {
+        x: Value<Fp>,
+        t: Fp,
+        coeffs_a: Vec<Fp>,
+        coeffs_b: Vec<Fp>,
+    }
+
+    impl Circuit<Fp> for PiecewiseCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                x: Value::unknown(),
+                t: self.t,
+                coeffs_a: self.coeffs_a.clone(),
+                coeffs_b: self.coeffs_b.clone(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+            let y = chip.eval_piecewise(
+                layouter.namespace(|| "piecewise"),
+                x,
+                self.t,
+                &self.coeffs_a,
+                &self.coeffs_b,
+            )?;
+
+            chip.expose_public(layouter.namespace(|| "expose y"), y, 0)
+        }
+    }
+
+    #[test]
+    fn test_eval_piecewise() {
+        let k = 8;
+        // x + 1 below 5, x^2 from 5 on
+        let circuit = |x: u64| PiecewiseCircuit {
+            x: Value::known(Fp::from(x)),
+            t: Fp::from(5),
+            coeffs_a: vec![Fp::one(), Fp::one()],
+            coeffs_b: vec![Fp::zero(), Fp::zero(), Fp::one()],
+        };
+
+        for (x, y, other) in [(3, 4, 9), (4, 5, 16), (5, 25, 6), (7, 49, 8)] {
+            let prover = MockProver::run(k, &circuit(x), vec![vec![Fp::from(y)]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "x = {}", x);
+
+            // the other branch's value is rejected
+            let prover = MockProver::run(k, &circuit(x), vec![vec![Fp::from(other)]]).unwrap();
+            assert!(prover.verify().is_err(), "x = {}", x);
+        }
+    }
+
+    // a is exposed as bytes at instance[0..32]
+    struct ExposeBytesCircuit {
+        a: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for ExposeBytesCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                a: Value::unknown(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let a = chip.load_private(layouter.namespace(|| "load a"), self.a)?;
+            chip.expose_bytes(layouter.namespace(|| "expose bytes"), a, 0)
+        }
+    }
+
+    #[test]
+    fn test_expose_bytes() {
+        let k = 9;
+        let circuit = ExposeBytesCircuit {
+            a: Value::known(Fp::from(35)),
+        };
+
+        let mut bytes = vec![Fp::zero(); 32];
+        bytes[31] = Fp::from(35);
+        let prover = MockProver::run(k, &circuit, vec![bytes.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // 35 = 0x23 is not 0x2300
+        bytes[30] = Fp::from(35);
+        bytes[31] = Fp::zero();
+        let prover = MockProver::run(k, &circuit, vec![bytes]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // 2 * a + 3 * b at instance[0]
+    #[derive(Default)]
+    struct WeightedSumCircuit {
+        a: Value<Fp>,
+        b: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for WeightedSumCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let nums =
+                chip.load_private_many(layouter.namespace(|| "load a, b"), &[self.a, self.b])?;
+            let weights = [Fp::from(2), Fp::from(3)];
+            let sum = chip.weighted_sum(layouter.namespace(|| "2a + 3b"), &nums, &weights)?;
+
+            chip.expose_public(layouter.namespace(|| "expose sum"), sum, 0)
+        }
+    }
+
+    #[test]
+    fn test_weighted_sum() {
+        let k = 4;
+        let circuit = WeightedSumCircuit {
+            a: Value::known(Fp::from(5)),
+            b: Value::known(Fp::from(7)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(31)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // swapped weights
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(29)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // the antiderivative of 1 + x at a private x at instance[0]
+    #[derive(Default)]
+    struct AntiderivativeCircuit {
+        x: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for AntiderivativeCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+            let coeffs = [Fp::one(), Fp::one()];
+            let integral =
+                chip.eval_antiderivative(layouter.namespace(|| "x + x^2 / 2"), x, &coeffs)?;
+
+            chip.expose_public(layouter.namespace(|| "expose integral"), integral, 0)
+        }
+    }
+
+    #[test]
+    fn test_eval_antiderivative() {
+        let k = 5;
+        let circuit = AntiderivativeCircuit {
+            x: Value::known(Fp::from(3)),
+        };
+
+        // 3 + 9 / 2 = 15 / 2
+        let half = Fp::from(2).invert().unwrap();
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(15) * half]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // the integer division 9 / 2 = 4 does not hold in the field
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(7)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // the interpolation of (1, 2), (2, 5), (3, 10) at a private point at instance[0]
+    #[derive(Default)]
+    struct LagrangeCircuit {
+        at: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for LagrangeCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let at = chip.load_private(layouter.namespace(|| "load at"), self.at)?;
+            let xs = [1, 2, 3].map(Fp::from);
+            let ys = [2, 5, 10].map(Fp::from);
+            let y = chip.eval_lagrange(layouter.namespace(|| "interpolate"), &xs, &ys, at)?;
+
+            chip.expose_public(layouter.namespace(|| "expose y"), y, 0)
+        }
+    }
+
+    #[test]
+    fn test_eval_lagrange() {
+        let k = 6;
+        // the points lie on x^2 + 1
+        let circuit = LagrangeCircuit {
+            at: Value::known(Fp::from(4)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(17)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(16)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // opens the public commitment to a private value and blinding
+    struct CommitmentCircuit {
+        value: Value<Fp>,
+        blinding: Value<Fp>,
+        commitment: Fp,
+    }
+
+    impl Circuit<Fp> for CommitmentCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                value: Value::unknown(),
+                blinding: Value::unknown(),
+                commitment: self.commitment,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let value = chip.load_private(layouter.namespace(|| "load value"), self.value)?;
+            let blinding =
+                chip.load_private(layouter.namespace(|| "load blinding"), self.blinding)?;
+            chip.verify_commitment(
+                layouter.namespace(|| "open"),
+                value,
+                blinding,
+                self.commitment,
+            )
+        }
+    }
+
+    #[test]
+    fn test_verify_commitment() {
+        let k = 4;
+        let blinding = Fp::from(0xb11d);
+        let commitment = commit(Fp::from(3), blinding);
+        let circuit = |value: Fp| CommitmentCircuit {
+            value: Value::known(value),
+            blinding: Value::known(blinding),
+            commitment,
+        };
+
+        let prover = MockProver::run(k, &circuit(Fp::from(3)), vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(k, &circuit(Fp::from(4)), vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // a and b are coprime, with a private Bezout witness (u, v)
+    #[derive(Default)]
+    struct CoprimeCircuit {
+        a: Value<Fp>,
+        b: Value<Fp>,
+        u: Value<Fp>,
+        v: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for CoprimeCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let nums = chip.load_private_many(
+                layouter.namespace(|| "load a, b, u, v"),
+                &[self.a, self.b, self.u, self.v],
+            )?;
+            let [a, b, u, v] = <[Number; 4]>::try_from(nums).unwrap();
+            chip.coprime_witness(layouter.namespace(|| "gcd(a, b) == 1"), a, b, u, v)
+        }
+    }
+
+    #[test]
+    fn test_coprime_witness() {
+        let k = 10;
+        let circuit = |a: u64, b: u64, u: Fp, v: Fp| CoprimeCircuit {
+            a: Value::known(Fp::from(a)),
+            b: Value::known(Fp::from(b)),
+            u: Value::known(u),
+            v: Value::known(v),
+        };
+
+        // 35 * 3 - 52 * 2 = 1
+        let prover =
+            MockProver::run(k, &circuit(35, 52, Fp::from(3), -Fp::from(2)), vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // gcd(35, 49) = 7, yet the field inverse of 35 satisfies the identity
+        let u = Fp::from(35).invert().unwrap();
+        let prover = MockProver::run(k, &circuit(35, 49, u, Fp::zero()), vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // a != 0 for a private a
+    struct NonzeroCircuit {
+        a: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for NonzeroCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                a: Value::unknown(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let a = chip.load_private(layouter.namespace(|| "load a"), self.a)?;
+            chip.assert_nonzero(layouter.namespace(|| "a != 0"), a)
+        }
+    }
+
+    #[test]
+    fn test_assert_nonzero() {
+        let k = 4;
+        for (a, ok) in [(Fp::from(7), true), (-Fp::one(), true), (Fp::zero(), false)] {
+            let circuit = NonzeroCircuit { a: Value::known(a) };
+            let prover = MockProver::run(k, &circuit, vec![vec![]]).unwrap();
+            assert_eq!(prover.verify().is_ok(), ok, "a = {:?}", a);
+        }
+    }
+
+    // a < 2^bits for a private a
+    struct RangeCheckCircuit {
+        a: Value<Fp>,
+        bits: usize,
+    }
+
+    impl Circuit<Fp> for RangeCheckCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                a: Value::unknown(),
+                bits: self.bits,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let a = chip.load_private(layouter.namespace(|| "load a"), self.a)?;
+            chip.range_check(layouter.namespace(|| "range"), a, self.bits)
+        }
+    }
+
+    #[test]
+    fn test_range_check() {
+        let k = 4;
+        for (a, ok) in [(0, true), (7, true), (8, false)] {
+            let circuit = RangeCheckCircuit {
+                a: Value::known(Fp::from(a)),
+                bits: 3,
+            };
+            let prover = MockProver::run(k, &circuit, vec![vec![]]).unwrap();
+            assert_eq!(prover.verify().is_ok(), ok, "a = {}", a);
+        }
+
+        // the field element -1 is far above any u64
+        let circuit = RangeCheckCircuit {
+            a: Value::known(-Fp::one()),
+            bits: 3,
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_circuit_builder() {
+        let k = 4;
+        let public = vec![vec![Fp::from(35)]];
+
+        let manual = MyCircuit {
+            constant: Fp::from(5),
+            x: Value::known(Fp::from(3)),
+        };
+        let built = CircuitBuilder::new().x(3).constant(5).build();
+        assert_eq!(built.constant, manual.constant);
+        built
+            .x
+            .zip(manual.x)
+            .assert_if_known(|(built, manual)| built == manual);
+
+        for circuit in [manual, built] {
+            let prover = MockProver::run(k, &circuit, public.clone()).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+
+        let circuit = CircuitBuilder::new()
+            .x(3)
+            .coeffs(&[5, 1, 0, 1])
+            .build_polynomial();
+        assert_eq!(circuit.coeffs, [5, 1, 0, 1].map(Fp::from).to_vec());
+        let prover = MockProver::run(5, &circuit, public).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    // Runs MyCircuit (constant 5) for x against every public output within 5 of
+    // `expected`, and asserts that only `expected` verifies.
+    fn assert_unique_output(k: u32, x: u64, expected: u64) {
+        let circuit = MyCircuit {
+            constant: Fp::from(5),
+            x: Value::known(Fp::from(x)),
+        };
+
+        for output in expected.saturating_sub(5)..=expected + 5 {
+            let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(output)]]).unwrap();
+            assert_eq!(
+                prover.verify().is_ok(),
+                output == expected,
+                "x = {}, public output = {}",
+                x,
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn test_unique_output() {
+        assert_unique_output(4, 3, 35);
+        assert_unique_output(4, 0, 5);
+        assert_unique_output(4, 10, 1015);
+    }
+
+    // Random x and constant across the whole field: verification must succeed
+    // exactly when the public input is x^3 + x + constant.
+    #[test]
+    fn test_fuzz_my_circuit() {
+        const ITERATIONS: usize = 200;
+        let mut rng = ChaCha8Rng::seed_from_u64(SEED);
+
+        for i in 0..ITERATIONS {
+            let x = Fp::random(&mut rng);
+            let constant = Fp::random(&mut rng);
+            let res = x * x * x + x + constant;
+            // every other iteration offers a wrong result
+            let public = if i % 2 == 0 {
+                res
+            } else {
+                res + Fp::random(&mut rng)
+            };
+
+            let circuit = MyCircuit {
+                constant,
+                x: Value::known(x),
+            };
+            let prover = MockProver::run(4, &circuit, vec![vec![public]]).unwrap();
+            assert_eq!(
+                prover.verify().is_ok(),
+                public == res,
+                "seed {:#x}, iteration {}: x = {:?}, constant = {:?}, public = {:?}",
+                SEED,
+                i,
+                x,
+                constant,
+                public
+            );
+        }
+    }
+
+    fn count(dump: &str, label: &str) -> usize {
+        dump_count(dump, label).unwrap()
+    }
+
+    // Locks in the shape of the chip. If this fails after adding a gate or a
+    // column on purpose, update the expected counts:
+    // - gates: "mul|add", "sub", "mul_add", "square_add_const", "select" and "range"
+    // - constraints: mul, add, sub, mul_add, square_add_const, two for select and
+    //   two for range
+    // - selectors: s_mul, s_add, s_sub, s_mul_add, s_square_add_const, s_select
+    //   and s_range
+    // - columns: 2 advice, 1 fixed (constants) and 1 instance
+    #[test]
+    fn test_circuit_shape() {
+        let gates = CircuitGates::collect::<Fp, MyCircuit>().to_string();
+        assert_eq!(count(&gates, "Total gates:"), 6);
+        assert_eq!(count(&gates, "Total custom constraint polynomials:"), 9);
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        MyCircuit::configure(&mut meta);
+        let pinned = format!("{:?}", meta.pinned());
+        assert_eq!(count(&pinned, "num_selectors:"), 7);
+        assert_eq!(count(&pinned, "num_advice_columns:"), 2);
+        assert_eq!(count(&pinned, "num_fixed_columns:"), 1);
+        assert_eq!(count(&pinned, "num_instance_columns:"), 1);
+    }
+
+    // The advice cells of a MockProver, as printed by its Debug output. The
+    // prover keeps them private, and the rest of the dump holds hash maps whose
+    // order changes between runs, so only this section is comparable.
+    fn advice_dump(prover: &MockProver<Fp>) -> String {
+        let dump = format!("{:?}", prover);
+        let start = dump.find("advice: ").expect("MockProver dump has advice");
+        let end = start + dump[start..].find(", instance: ").unwrap();
+        dump[start..end].to_string()
+    }
+
+    // Synthesizing the same circuit twice must assign the same advice values,
+    // cell by cell. This covers the witnesses derived inside the chip: the
+    // quotient and remainder of reduce_mod and the inverse of assert_nonzero.
+    #[test]
+    fn test_witness_determinism() {
+        let runs = |circuit: &dyn Fn() -> MockProver<Fp>| {
+            let (first, second) = (circuit(), circuit());
+            assert_eq!(first.verify(), Ok(()));
+            assert_eq!(advice_dump(&first), advice_dump(&second));
+        };
+
+        runs(&|| {
+            let circuit = MyCircuit {
+                constant: Fp::from(5),
+                x: Value::known(Fp::from(3)),
+            };
+            MockProver::run(4, &circuit, vec![vec![Fp::from(35)]]).unwrap()
+        });
+        runs(&|| {
+            let circuit = ReduceModCircuit {
+                a: Value::known(Fp::from(1000)),
+                p: Fp::from(7),
+            };
+            MockProver::run(8, &circuit, vec![vec![Fp::from(6)]]).unwrap()
+        });
+        runs(&|| {
+            let circuit = NonzeroCircuit {
+                a: Value::known(Fp::from(7)),
+            };
+            MockProver::run(4, &circuit, vec![vec![]]).unwrap()
+        });
+    }
+
+    #[test]
+    fn test_check_columns() {
+        assert_eq!(check_columns(2), Ok(()));
+        assert!(check_columns(1).is_err());
+    }
+
+    #[test]
+    fn test_load_instance() {
+        let k = 4;
+        let circuit = PublicInputCircuit {
+            constant: Fp::from(5),
+        };
+
+        let public_inputs = vec![Fp::from(3), Fp::from(35)];
+        let prover = MockProver::run(k, &circuit, vec![public_inputs]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // the result does not match the public x
+        let public_inputs = vec![Fp::from(5), Fp::from(35)];
+        let prover = MockProver::run(k, &circuit, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,1065 @@
+use halo2_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use halo2_proofs::pasta::group::ff::{FromUniformBytes, PrimeField};
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::{Circuit, ConstraintSystem, Error};
+use polynomial_core::PolynomialSpec;
+
+use crate::{to_u64, usable_rows, FieldChip, FieldConfig, MyCircuit, NumbericInstructions};
+
+/// Evaluates the polynomial outside of a circuit, as a reference for the gates.
+pub fn eval_generic(coeffs: &[Fp], x: Fp) -> Fp {
+    coeffs.iter().rev().fold(Fp::zero(), |acc, c| acc * x + c)
+}
+
+/// Proves y = coeffs[0] + coeffs[1] * x + ... + coeffs[n] * x^n for a private x,
+/// exposing y at instance[0].
+#[derive(Clone, Debug, Default)]
+pub struct PolynomialCircuit {
+    pub coeffs: Vec<Fp>,
+    pub x: Value<Fp>,
+    /// Evaluates a template of [`Self::padded_len`] coefficients filling `2^k`
+    /// rows instead, so that every polynomial that fits shares one verifying
+    /// key. The coefficients are then read from instance[1..], padded with
+    /// zeros, see [`Self::instance`].
+    pub pad_to_k: Option<u32>,
+}
+
+impl PolynomialCircuit {
+    /// Returns how many coefficients the template padded to `2^k` rows holds.
+    pub fn padded_len(k: u32) -> Result<usize, Error> {
+        // x and each coefficient take one row, each Horner step two
+        Ok((usable_rows::<Self>(k)? + 1) / 3)
+    }
+
+    /// Returns the public inputs for the output `y`: `y` alone, or followed by
+    /// the coefficients padded to the template with [`Self::pad_to_k`].
+    pub fn instance(&self, y: Fp) -> Result<Vec<Fp>, Error> {
+        let mut public = vec![y];
+        if let Some(k) = self.pad_to_k {
+            let len = Self::padded_len(k)?;
+            if self.coeffs.len() > len {
+                return Err(Error::NotEnoughRowsAvailable { current_k: k });
+            }
+            public.extend(&self.coeffs);
+            public.resize(len + 1, Fp::zero());
+        }
+
+        Ok(public)
+    }
+}
+
+impl Circuit<Fp> for PolynomialCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        // the coefficients are constants of the circuit, only x is a witness
+        Self {
+            coeffs: self.coeffs.clone(),
+            x: Value::unknown(),
+            pad_to_k: self.pad_to_k,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+
+        let Some(k) = self.pad_to_k else {
+            let y = chip.eval_horner(layouter.namespace(|| "eval"), x, &self.coeffs)?;
+
+            // debug builds check the assigned y against the reference evaluation
+            #[cfg(debug_assertions)]
+            {
+                let expected = self.x.map(|x| eval_generic(&self.coeffs, x));
+                y.0.value()
+                    .zip(expected)
+                    .assert_if_known(|(y, expected)| **y == *expected);
+            }
+
+            return chip.expose_public(layouter.namespace(|| "expose y"), y, 0);
+        };
+
+        // the layout only depends on k, the coefficients are public inputs
+        let len = Self::padded_len(k)?;
+        if self.coeffs.len() > len {
+            return Err(Error::NotEnoughRowsAvailable { current_k: k });
+        }
+        let coeffs = (1..=len)
+            .map(|row| chip.load_instance(layouter.namespace(|| format!("coeff {}", row)), row))
+            .collect::<Result<Vec<_>, _>>()?;
+        let y = chip.eval_horner_assigned(layouter.namespace(|| "eval"), x, &coeffs)?;
+
+        chip.expose_public(layouter.namespace(|| "expose y"), y, 0)
+    }
+}
+
+/// Proves that a polynomial supplied by the verifier was evaluated at a private x.
+/// The `num_coeffs` coefficients are read from instance[0..num_coeffs], lowest
+/// degree first, and y is exposed at instance[num_coeffs].
+#[derive(Clone, Default)]
+pub struct PublicCoeffsCircuit {
+    pub num_coeffs: usize,
+    pub x: Value<Fp>,
+}
+
+impl Circuit<Fp> for PublicCoeffsCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            num_coeffs: self.num_coeffs,
+            x: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+        let coeffs = (0..self.num_coeffs)
+            .map(|row| chip.load_instance(layouter.namespace(|| format!("coeff {}", row)), row))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let y = chip.eval_horner_assigned(layouter.namespace(|| "eval"), x, &coeffs)?;
+
+        chip.expose_public(layouter.namespace(|| "expose y"), y, self.num_coeffs)
+    }
+}
+
+/// Proves that the polynomials p and q agree at a private x, without revealing
+/// x or the common value. Coefficients are lowest degree first.
+#[derive(Clone, Default)]
+pub struct EqualityCircuit {
+    pub p: Vec<Fp>,
+    pub q: Vec<Fp>,
+    pub x: Value<Fp>,
+}
+
+impl Circuit<Fp> for EqualityCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            p: self.p.clone(),
+            q: self.q.clone(),
+            x: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+        let p = chip.eval_horner(layouter.namespace(|| "eval p"), x.clone(), &self.p)?;
+        let q = chip.eval_horner(layouter.namespace(|| "eval q"), x, &self.q)?;
+
+        chip.assert_equal(layouter.namespace(|| "p(x) == q(x)"), p, q)
+    }
+}
+
+/// Proves that the public a at instance[0] is a quadratic residue by knowing
+/// a root with root^2 = a.
+#[derive(Clone, Default)]
+pub struct QrCircuit {
+    pub root: Value<Fp>,
+}
+
+impl Circuit<Fp> for QrCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let root = chip.load_private(layouter.namespace(|| "load root"), self.root)?;
+        let a = chip.square_add_const(layouter.namespace(|| "root^2"), root, Fp::zero())?;
+
+        chip.expose_public(layouter.namespace(|| "expose a"), a, 0)
+    }
+}
+
+/// Proves z = f(g(x)) for a private x, exposing z at instance[0]. The
+/// intermediate g(x) stays private. Coefficients are lowest degree first.
+#[derive(Clone, Default)]
+pub struct CompositionCircuit {
+    pub f: Vec<Fp>,
+    pub g: Vec<Fp>,
+    pub x: Value<Fp>,
+}
+
+impl Circuit<Fp> for CompositionCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            g: self.g.clone(),
+            x: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+        let y = chip.eval_horner(layouter.namespace(|| "eval g"), x, &self.g)?;
+        let z = chip.eval_horner(layouter.namespace(|| "eval f"), y, &self.f)?;
+
+        chip.expose_public(layouter.namespace(|| "expose z"), z, 0)
+    }
+}
+
+/// Proves y_i = f(x_i) for private points x_0, ..., x_n, exposing y_i at
+/// instance[i]. The number of points is part of the circuit, so one key serves
+/// every batch of that size.
+#[derive(Clone, Default)]
+pub struct MultiPointCircuit {
+    pub coeffs: Vec<Fp>,
+    pub points: Vec<Value<Fp>>,
+}
+
+impl Circuit<Fp> for MultiPointCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            coeffs: self.coeffs.clone(),
+            points: vec![Value::unknown(); self.points.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let points = chip.load_private_many(layouter.namespace(|| "load points"), &self.points)?;
+        for (row, x) in points.into_iter().enumerate() {
+            let y = chip.eval_horner(
+                layouter.namespace(|| format!("eval {}", row)),
+                x,
+                &self.coeffs,
+            )?;
+            chip.expose_public(layouter.namespace(|| format!("expose y {}", row)), y, row)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Proves that the private outputs y1 and y2 of two zkVM receipts sum to the
+/// total at instance[0]. The receipts are checked outside of the circuit, so
+/// this only aggregates values the prover already holds verified receipts for.
+#[derive(Clone, Default)]
+pub struct OutputSumCircuit {
+    pub y1: Value<Fp>,
+    pub y2: Value<Fp>,
+}
+
+impl Circuit<Fp> for OutputSumCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let y1 = chip.load_private(layouter.namespace(|| "load y1"), self.y1)?;
+        let y2 = chip.load_private(layouter.namespace(|| "load y2"), self.y2)?;
+        let total = chip.add(layouter.namespace(|| "y1 + y2"), y1, y2)?;
+
+        chip.expose_public(layouter.namespace(|| "expose total"), total, 0)
+    }
+}
+
+/// Proves that the private point (px, py) lies on the curve py^2 = px^3 + px + 5,
+/// the polynomial read as a short Weierstrass equation. Nothing is exposed.
+#[derive(Clone, Default)]
+pub struct CurvePointCircuit {
+    pub px: Value<Fp>,
+    pub py: Value<Fp>,
+}
+
+impl Circuit<Fp> for CurvePointCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let px = chip.load_private(layouter.namespace(|| "load px"), self.px)?;
+        let py = chip.load_private(layouter.namespace(|| "load py"), self.py)?;
+        let five = chip.load_constant(layouter.namespace(|| "load 5"), Fp::from(5))?;
+
+        let lhs = chip.square_add_const(layouter.namespace(|| "py^2"), py, Fp::zero())?;
+        // px^3 + px = (px^2 + 1) * px
+        let px2_plus_1 =
+            chip.square_add_const(layouter.namespace(|| "px^2 + 1"), px.clone(), Fp::one())?;
+        let px3_plus_px = chip.mul(layouter.namespace(|| "px^3 + px"), px2_plus_1, px)?;
+        let rhs = chip.add(layouter.namespace(|| "px^3 + px + 5"), px3_plus_px, five)?;
+
+        chip.assert_equal(layouter.namespace(|| "py^2 == px^3 + px + 5"), lhs, rhs)
+    }
+}
+
+/// Proves f(x) = (x - roots[0]) * ... * (x - roots[n]) at the point x given by
+/// [`Self::challenge`]. The coefficients and roots are circuit constants. By
+/// Schwartz-Zippel, two different polynomials of degree n agree on at most n
+/// points, so a random x catches a wrong factorization. The check is only sound
+/// if the prover cannot choose x, so x is hashed from the statement instead of
+/// being a witness.
+#[derive(Clone, Default)]
+pub struct FactorizationCircuit {
+    pub coeffs: Vec<Fp>,
+    pub roots: Vec<Fp>,
+}
+
+impl FactorizationCircuit {
+    /// Returns the evaluation point, a Blake2b hash of the coefficients and
+    /// the roots. Both are fixed by the verifying key, so is the point.
+    pub fn challenge(&self) -> Fp {
+        let mut state = blake2b_simd::Params::new()
+            .personal(b"Factorization-X")
+            .to_state();
+        state.update(&(self.coeffs.len() as u64).to_le_bytes());
+        for value in self.coeffs.iter().chain(&self.roots) {
+            state.update(value.to_repr().as_ref());
+        }
+
+        Fp::from_uniform_bytes(state.finalize().as_array())
+    }
+}
+
+impl Circuit<Fp> for FactorizationCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let x = chip.load_constant(layouter.namespace(|| "load x"), self.challenge())?;
+        let y = chip.eval_horner(layouter.namespace(|| "eval f"), x.clone(), &self.coeffs)?;
+
+        let roots = chip.load_constants(layouter.namespace(|| "roots"), &self.roots)?;
+        let factors = roots
+            .into_iter()
+            .map(|r| chip.sub(layouter.namespace(|| "x - r"), x.clone(), r))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (first, rest) = factors.split_first().ok_or(Error::Synthesis)?;
+        let mut product = first.clone();
+        for factor in rest {
+            product = chip.mul(
+                layouter.namespace(|| "prod(x - r)"),
+                product,
+                factor.clone(),
+            )?;
+        }
+
+        chip.assert_equal(layouter.namespace(|| "f(x) == prod(x - r)"), y, product)
+    }
+}
+
+/// Proves knowledge of a private w below `2^bits` with g^w = target, the
+/// discrete log of the target at instance[0] to the base g. g is a circuit
+/// constant. In a field of this size the log is only hidden if w is not small
+/// enough to brute force.
+#[derive(Clone, Default)]
+pub struct DiscreteLogCircuit {
+    pub g: Fp,
+    pub bits: usize,
+    pub w: Value<Fp>,
+}
+
+impl Circuit<Fp> for DiscreteLogCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            g: self.g,
+            bits: self.bits,
+            w: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let w = chip.load_private(layouter.namespace(|| "load w"), self.w)?;
+        let target = chip.pow_private(layouter.namespace(|| "g^w"), self.g, w, self.bits)?;
+
+        chip.expose_public(layouter.namespace(|| "expose target"), target, 0)
+    }
+}
+
+/// Proves that y = f(x) is prime for a private x, exposing y at instance[0].
+/// y must be at most `max_divisor^2`, so that the divisors in `[2, max_divisor]`
+/// cover `[2, sqrt(y)]`. Every divisor d with `d^2 <= y` must leave a nonzero
+/// remainder; a witnessed bit, range checked against `y - d^2`, tells which
+/// divisors those are. The circuit grows linearly with `max_divisor`.
+#[derive(Clone, Default)]
+pub struct PrimalityCircuit {
+    pub coeffs: Vec<Fp>,
+    pub x: Value<Fp>,
+    pub max_divisor: u64,
+}
+
+impl Circuit<Fp> for PrimalityCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            coeffs: self.coeffs.clone(),
+            x: Value::unknown(),
+            max_divisor: self.max_divisor,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+        let max_square = self
+            .max_divisor
+            .checked_mul(self.max_divisor)
+            .ok_or(Error::Synthesis)?;
+
+        let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+        let y = chip.eval_horner(layouter.namespace(|| "eval"), x, &self.coeffs)?;
+
+        // 2 <= y <= max_divisor^2
+        let two = chip.load_constant(layouter.namespace(|| "2"), Fp::from(2))?;
+        let max_square =
+            chip.load_constant(layouter.namespace(|| "max^2"), Fp::from(max_square))?;
+        let above_two = chip.sub(layouter.namespace(|| "y - 2"), y.clone(), two)?;
+        let below_max = chip.sub(layouter.namespace(|| "max^2 - y"), max_square, y.clone())?;
+        chip.range_check(layouter.namespace(|| "y - 2"), above_two, 64)?;
+        chip.range_check(layouter.namespace(|| "max^2 - y"), below_max, 64)?;
+
+        let one = chip.load_constant(layouter.namespace(|| "1"), Fp::one())?;
+        for divisor in 2..=self.max_divisor {
+            let square = divisor * divisor;
+            let in_range = chip.witness(layouter.namespace(|| "d^2 <= y"), || {
+                y.0.value()
+                    .map(|y| Fp::from(u64::from(to_u64(y).is_some_and(|y| square <= y))))
+            })?;
+
+            // d^2 <= y exactly when y - d^2 fits in 64 bits, and d^2 > y when
+            // d^2 - 1 - y does; the select gate also makes the bit boolean
+            let square = chip.load_constant(layouter.namespace(|| "d^2"), Fp::from(square))?;
+            let below = chip.sub(
+                layouter.namespace(|| "d^2 - 1"),
+                square.clone(),
+                one.clone(),
+            )?;
+            let below = chip.sub(layouter.namespace(|| "d^2 - 1 - y"), below, y.clone())?;
+            let above = chip.sub(layouter.namespace(|| "y - d^2"), y.clone(), square)?;
+            let gap = chip.select(
+                layouter.namespace(|| "in range ? y - d^2 : d^2 - 1 - y"),
+                in_range.clone(),
+                above,
+                below,
+            )?;
+            chip.range_check(layouter.namespace(|| "gap"), gap, 64)?;
+
+            let r = chip.reduce_mod(
+                layouter.namespace(|| format!("y mod {}", divisor)),
+                y.clone(),
+                Fp::from(divisor),
+            )?;
+            // divisors above sqrt(y) are skipped by checking 1 instead
+            let checked = chip.select(
+                layouter.namespace(|| "in range ? r : 1"),
+                in_range,
+                r,
+                one.clone(),
+            )?;
+            chip.assert_nonzero(layouter.namespace(|| "r != 0"), checked)?;
+        }
+
+        chip.expose_public(layouter.namespace(|| "expose y"), y, 0)
+    }
+}
+
+/// Proves the evaluation of a [`PolynomialSpec`] at a private x, exposing the
+/// (reduced) y at instance[0]. x is checked against the bound, and y must fit
+/// in a u64. This matches the zkVM guest as long as the unreduced f(x) stays
+/// below the field modulus, which holds for any spec the guest accepts with
+/// small coefficients but is not enforced for intermediate values.
+#[derive(Clone, Default)]
+pub struct SpecCircuit {
+    pub spec: PolynomialSpec,
+    pub x: Value<Fp>,
+}
+
+impl Circuit<Fp> for SpecCircuit {
+    type Config = FieldConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            spec: self.spec.clone(),
+            x: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+        MyCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: FieldConfig,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let chip = FieldChip::new(config);
+
+        let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+        chip.range_check(layouter.namespace(|| "x"), x.clone(), 64)?;
+        if let Some(bound) = self.spec.bound {
+            // x <= bound as bound - x does not wrap around
+            let bound = chip.load_constant(layouter.namespace(|| "bound"), Fp::from(bound))?;
+            let slack = chip.sub(layouter.namespace(|| "bound - x"), bound, x.clone())?;
+            chip.range_check(layouter.namespace(|| "bound - x"), slack, 64)?;
+        }
+
+        let coeffs: Vec<Fp> = self.spec.coeffs.iter().map(|c| Fp::from(*c)).collect();
+        let mut y = chip.eval_horner(layouter.namespace(|| "eval"), x, &coeffs)?;
+        chip.range_check(layouter.namespace(|| "y"), y.clone(), 64)?;
+        if let Some(modulus) = self.spec.modulus {
+            y = chip.reduce_mod(layouter.namespace(|| "y mod m"), y, Fp::from(modulus))?;
+        }
+
+        chip.expose_public(layouter.namespace(|| "expose y"), y, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::arithmetic::Field;
+    use halo2_proofs::dev::MockProver;
+    use proptest::prelude::*;
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::{OsRng, SeedableRng};
+
+    fn any_fp() -> impl Strategy<Value = Fp> {
+        any::<[u64; 4]>().prop_map(Fp::from_raw)
+    }
+
+    // y = x^3 + x + 5 must verify exactly when y = f(x). proptest shrinks a failing
+    // case and prints the x and offset it found.
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_polynomial_circuit_soundness(x in any_fp(), offset in any_fp()) {
+            let coeffs = [5, 1, 0, 1].map(Fp::from).to_vec();
+            let y = eval_generic(&coeffs, x) + offset;
+            let circuit = PolynomialCircuit {
+                coeffs,
+                x: Value::known(x),
+                pad_to_k: None,
+            };
+
+            let prover = MockProver::run(5, &circuit, vec![vec![y]]).unwrap();
+            prop_assert_eq!(prover.verify().is_ok(), offset == Fp::zero());
+        }
+
+        #[test]
+        fn prop_polynomial_circuit_completeness(x in any_fp()) {
+            let coeffs = [5, 1, 0, 1].map(Fp::from).to_vec();
+            let y = eval_generic(&coeffs, x);
+            let circuit = PolynomialCircuit {
+                coeffs,
+                x: Value::known(x),
+                pad_to_k: None,
+            };
+
+            let prover = MockProver::run(5, &circuit, vec![vec![y]]).unwrap();
+            prop_assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_polynomial_circuit() {
+        // y = x^3 + x + 5
+        let coeffs = [5, 1, 0, 1].map(Fp::from).to_vec();
+        let circuit = PolynomialCircuit {
+            coeffs,
+            x: Value::known(Fp::from(3)),
+            pad_to_k: None,
+        };
+
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(35)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(36)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    // y = x^3 + x + 5 at instance[0] and y^2 at instance[1]
+    #[derive(Default)]
+    struct EvalAndSquareCircuit {
+        x: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for EvalAndSquareCircuit {
+        type Config = FieldConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> FieldConfig {
+            MyCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: FieldConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FieldChip::new(config);
+
+            let x = chip.load_private(layouter.namespace(|| "load x"), self.x)?;
+            let coeffs = [5, 1, 0, 1].map(Fp::from);
+            let (y, y2) = chip.eval_and_square(layouter.namespace(|| "eval"), x, &coeffs)?;
+
+            chip.expose_public(layouter.namespace(|| "expose y"), y, 0)?;
+            chip.expose_public(layouter.namespace(|| "expose y^2"), y2, 1)
+        }
+    }
+
+    #[test]
+    fn test_eval_and_square() {
+        let circuit = EvalAndSquareCircuit {
+            x: Value::known(Fp::from(3)),
+        };
+
+        let public = vec![Fp::from(35), Fp::from(1225)];
+        let prover = MockProver::run(5, &circuit, vec![public]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let public = vec![Fp::from(35), Fp::from(1224)];
+        let prover = MockProver::run(5, &circuit, vec![public]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_eval_generic() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0x5eed);
+
+        for degree in 0..8 {
+            let coeffs: Vec<Fp> = (0..=degree).map(|_| Fp::random(&mut rng)).collect();
+            let x = Fp::random(&mut rng);
+            let circuit = PolynomialCircuit {
+                coeffs: coeffs.clone(),
+                x: Value::known(x),
+                pad_to_k: None,
+            };
+
+            let public = vec![eval_generic(&coeffs, x)];
+            let prover = MockProver::run(6, &circuit, vec![public]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_public_coeffs_circuit() {
+        let circuit = PublicCoeffsCircuit {
+            num_coeffs: 4,
+            x: Value::known(Fp::from(3)),
+        };
+
+        // x^3 + x + 5 followed by y
+        let public = [5, 1, 0, 1, 35].map(Fp::from).to_vec();
+        let prover = MockProver::run(5, &circuit, vec![public]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // x^3 + 2x + 5 evaluates to 38 at x = 3
+        let public = [5, 2, 0, 1, 35].map(Fp::from).to_vec();
+        let prover = MockProver::run(5, &circuit, vec![public]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_equality_circuit() {
+        let x = Value::known(Fp::random(OsRng));
+
+        // (x + 1)^2 and x^2 + 2x + 1
+        let circuit = EqualityCircuit {
+            p: vec![Fp::one(), Fp::from(2), Fp::one()],
+            q: vec![Fp::one(), Fp::from(2), Fp::one()],
+            x,
+        };
+        let prover = MockProver::run(5, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // x^2 + 2x + 1 and x^2 + 1 only agree at x = 0
+        let circuit = EqualityCircuit {
+            p: vec![Fp::one(), Fp::from(2), Fp::one()],
+            q: vec![Fp::one(), Fp::zero(), Fp::one()],
+            x,
+        };
+        let prover = MockProver::run(5, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_qr_circuit() {
+        let a = Fp::from(49);
+        // both square roots of 49 pass
+        for root in [Fp::from(7), -Fp::from(7)] {
+            let circuit = QrCircuit {
+                root: Value::known(root),
+            };
+            let prover = MockProver::run(4, &circuit, vec![vec![a]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+
+        let circuit = QrCircuit {
+            root: Value::known(Fp::from(6)),
+        };
+        let prover = MockProver::run(4, &circuit, vec![vec![a]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_composition_circuit() {
+        // g(x) = x + 1 and f(y) = y^2
+        let circuit = CompositionCircuit {
+            f: vec![Fp::zero(), Fp::zero(), Fp::one()],
+            g: vec![Fp::one(), Fp::one()],
+            x: Value::known(Fp::from(2)),
+        };
+
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(9)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // g(f(2)) = 5
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(5)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_multi_point_circuit() {
+        let k = 6;
+        let coeffs = [5, 1, 0, 1].map(Fp::from).to_vec();
+        let circuit = MultiPointCircuit {
+            coeffs,
+            points: [2, 3, 4].map(|x| Value::known(Fp::from(x))).to_vec(),
+        };
+
+        let public = [15, 35, 73].map(Fp::from).to_vec();
+        let prover = MockProver::run(k, &circuit, vec![public]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // the results in another order
+        let public = [35, 15, 73].map(Fp::from).to_vec();
+        let prover = MockProver::run(k, &circuit, vec![public]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_output_sum_circuit() {
+        let k = 4;
+        // f(3) = 35 and f(4) = 73
+        let circuit = OutputSumCircuit {
+            y1: Value::known(Fp::from(35)),
+            y2: Value::known(Fp::from(73)),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(108)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(k, &circuit, vec![vec![Fp::from(109)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_curve_point_circuit() {
+        let k = 4;
+        // no integer point lies on the curve, so take the first px with a square root
+        let (px, py) = (0u64..)
+            .map(Fp::from)
+            .find_map(|px| {
+                Option::<Fp>::from((px * px * px + px + Fp::from(5)).sqrt()).map(|py| (px, py))
+            })
+            .unwrap();
+
+        let circuit = CurvePointCircuit {
+            px: Value::known(px),
+            py: Value::known(py),
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let circuit = CurvePointCircuit {
+            px: Value::known(px),
+            py: Value::known(py + Fp::one()),
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_factorization_circuit() {
+        let k = 6;
+        // (x - 1)(x - 2)(x - 3) = x^3 - 6x^2 + 11x - 6
+        let coeffs = vec![-Fp::from(6), Fp::from(11), -Fp::from(6), Fp::one()];
+        let circuit = |roots: [u64; 3]| FactorizationCircuit {
+            coeffs: coeffs.clone(),
+            roots: roots.map(Fp::from).to_vec(),
+        };
+
+        let prover = MockProver::run(k, &circuit([1, 2, 3]), vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // the wrong factorization agrees with f at the shared roots 1 and 2, a
+        // prover choosing x could pass with either
+        let wrong = circuit([1, 2, 4]);
+        let factored = |x: Fp| wrong.roots.iter().map(|r| x - r).product::<Fp>();
+        for x in [Fp::one(), Fp::from(2)] {
+            assert_eq!(eval_generic(&coeffs, x), factored(x));
+        }
+        let x = wrong.challenge();
+        assert_ne!(eval_generic(&coeffs, x), factored(x));
+        let prover = MockProver::run(k, &wrong, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_discrete_log_circuit() {
+        let k = 7;
+        let circuit = |w: u64| DiscreteLogCircuit {
+            g: Fp::from(3),
+            bits: 8,
+            w: Value::known(Fp::from(w)),
+        };
+
+        // 3^13 = 1594323
+        let target = vec![Fp::from(1_594_323)];
+        let prover = MockProver::run(k, &circuit(13), vec![target.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        let prover = MockProver::run(k, &circuit(12), vec![target.clone()]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // the low 8 bits of 269 = 256 + 13 raise 3 to the target, but 269 does
+        // not fit in 8 bits
+        let prover = MockProver::run(k, &circuit(269), vec![target]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_primality_circuit() {
+        let k = 11;
+        let coeffs = [5, 1, 0, 1].map(Fp::from).to_vec();
+        let circuit = |x: u64| PrimalityCircuit {
+            coeffs: coeffs.clone(),
+            x: Value::known(Fp::from(x)),
+            max_divisor: 9,
+        };
+
+        // f(4) = 73 is prime, f(3) = 35 = 5 * 7 is not
+        let prover = MockProver::run(k, &circuit(4), vec![vec![Fp::from(73)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        let prover = MockProver::run(k, &circuit(3), vec![vec![Fp::from(35)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // f(6) = 227 is prime, but above 9^2, so the divisors do not cover its
+        // square root
+        let prover = MockProver::run(k, &circuit(6), vec![vec![Fp::from(227)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // f(0) = 5 and f(1) = 7 are primes below the largest divisor, which
+        // only divide themselves
+        let prover = MockProver::run(k, &circuit(0), vec![vec![Fp::from(5)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        let prover = MockProver::run(k, &circuit(1), vec![vec![Fp::from(7)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_spec_circuit() {
+        let spec: PolynomialSpec = toml::from_str(
+            "
+            coeffs = [5, 1, 0, 1]
+            bound = 100
+            modulus = 1000
+            ",
+        )
+        .unwrap();
+
+        // 10^3 + 10 + 5 = 1015
+        let circuit = SpecCircuit {
+            spec: spec.clone(),
+            x: Value::known(Fp::from(10)),
+        };
+        let prover = MockProver::run(9, &circuit, vec![vec![Fp::from(15)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // 101 is above the bound, even with the correct 1030407 mod 1000
+        let circuit = SpecCircuit {
+            spec,
+            x: Value::known(Fp::from(101)),
+        };
+        let public = vec![Fp::from(407)];
+        let prover = MockProver::run(9, &circuit, vec![public]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_padding_to_common_k() {
+        let k = 6;
+        let x = Fp::from(2);
+
+        let mut digests = vec![];
+        for coeffs in [vec![1, 2, 3], vec![1, 2, 3, 4, 5]] {
+            let coeffs: Vec<Fp> = coeffs.into_iter().map(Fp::from).collect();
+            let y = eval_generic(&coeffs, x);
+            let circuit = PolynomialCircuit {
+                coeffs,
+                x: Value::known(x),
+                pad_to_k: Some(k),
+            };
+
+            let public = circuit.instance(y).unwrap();
+            let prover = MockProver::run(k, &circuit, vec![public.clone()]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+            let prover =
+                MockProver::run(k, &circuit, vec![circuit.instance(y + Fp::one()).unwrap()])
+                    .unwrap();
+            assert!(prover.verify().is_err());
+
+            let (_, pk) = crate::proof::setup(k, &circuit).unwrap();
+            digests.push(crate::bundle::vk_digest(pk.get_vk()));
+        }
+        assert_eq!(digests[0], digests[1]);
+
+        // the template fills its k, a polynomial of one more coefficient does not fit
+        let len = PolynomialCircuit::padded_len(k).unwrap();
+        let full = PolynomialCircuit {
+            coeffs: vec![Fp::one(); len],
+            x: Value::known(x),
+            pad_to_k: Some(k),
+        };
+        let public = full.instance(eval_generic(&full.coeffs, x)).unwrap();
+        let prover = MockProver::run(k, &full, vec![public.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        assert_eq!(crate::proof::minimal_k(&full, &public), Some(k));
+
+        let over = PolynomialCircuit {
+            coeffs: vec![Fp::one(); len + 1],
+            ..full
+        };
+        assert!(over.instance(Fp::zero()).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::pasta::{EqAffine, Fp};
+use halo2_proofs::plonk::{
+    create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, Error, ProvingKey, SingleVerifier,
+    VerificationStrategy, VerifyingKey,
+};
+use halo2_proofs::poly::commitment::{Guard, Params, MSM};
+use halo2_proofs::transcript::{Blake2bRead, Blake2bWrite, Challenge255, EncodedChallenge};
+use polynomial_core::{PolynomialResult, PolynomialSpec};
+use rand_core::OsRng;
+
+use crate::polynomial::SpecCircuit;
+
+/// The largest `k` tried by [`minimal_k`].
+pub const MAX_K: u32 = 18;
+
+/// Returns the smallest `k` for which the circuit fits in `2^k` rows.
+pub fn minimal_k<C: Circuit<Fp>>(circuit: &C, public: &[Fp]) -> Option<u32> {
+    (1..=MAX_K).find(|&k| MockProver::run(k, circuit, vec![public.to_vec()]).is_ok())
+}
+
+/// Generates the params and the proving key for the circuit.
+pub fn setup<C: Circuit<Fp>>(
+    k: u32,
+    circuit: &C,
+) -> Result<(Params<EqAffine>, ProvingKey<EqAffine>), Error> {
+    let params = Params::new(k);
+    let vk = keygen_vk(&params, circuit)?;
+    let pk = keygen_pk(&params, vk, circuit)?;
+
+    Ok((params, pk))
+}
+
+/// Creates a proof that the circuit is satisfied with the given public inputs.
+pub fn prove<C: Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuit: C,
+    public: &[Fp],
+) -> Result<Vec<u8>, Error> {
+    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+    create_proof(params, pk, &[circuit], &[&[public]], OsRng, &mut transcript)?;
+
+    Ok(transcript.finalize())
+}
+
+/// How the final multi-scalar multiplication of a proof is checked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VerifyStrategy {
+    /// Check everything at once with [`SingleVerifier`].
+    #[default]
+    Single,
+    /// Split off the accumulator `G` of the inner product argument, as a
+    /// recursive verifier would, and check the remaining MSM against it.
+    Accumulator,
+}
+
+/// Verifies a proof created by [`prove`] with [`VerifyStrategy::Single`].
+pub fn verify(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proof: &[u8],
+    public: &[Fp],
+) -> Result<(), Error> {
+    verify_with(params, vk, proof, public, VerifyStrategy::default())
+}
+
+/// Verifies a proof created by [`prove`] with the given strategy.
+pub fn verify_with(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proof: &[u8],
+    public: &[Fp],
+    strategy: VerifyStrategy,
+) -> Result<(), Error> {
+    let mut transcript = Blake2bRead::<_, EqAffine, Challenge255<_>>::init(proof);
+
+    match strategy {
+        VerifyStrategy::Single => {
+            let strategy = SingleVerifier::new(params);
+            verify_proof(params, vk, strategy, &[&[public]], &mut transcript)
+        }
+        VerifyStrategy::Accumulator => {
+            let strategy = AccumulatorVerifier {
+                msm: MSM::new(params),
+            };
+            verify_proof(params, vk, strategy, &[&[public]], &mut transcript).map(|_| ())
+        }
+    }
+}
+
+// Yields the accumulator G instead of folding the challenges into the MSM.
+// Here G is computed on the spot; on a recursion path it is supplied by the
+// prover and its computation is deferred to the next layer.
+struct AccumulatorVerifier<'params> {
+    msm: MSM<'params, EqAffine>,
+}
+
+impl<'params> VerificationStrategy<'params, EqAffine> for AccumulatorVerifier<'params> {
+    type Output = EqAffine;
+
+    fn process<E: EncodedChallenge<EqAffine>>(
+        self,
+        f: impl FnOnce(MSM<'params, EqAffine>) -> Result<Guard<'params, EqAffine, E>, Error>,
+    ) -> Result<Self::Output, Error> {
+        let guard = f(self.msm)?;
+        let g = guard.compute_g();
+        let (msm, _) = guard.use_g(g);
+
+        if msm.eval() {
+            Ok(g)
+        } else {
+            Err(Error::ConstraintSystemFailure)
+        }
+    }
+}
+
+/// A proof of a [`PolynomialSpec`] evaluation, together with what is needed
+/// to verify it.
+pub struct SpecProof {
+    pub params: Params<EqAffine>,
+    pub vk: VerifyingKey<EqAffine>,
+    pub proof: Vec<u8>,
+    pub y: u64,
+}
+
+/// Proves the evaluation of `spec` at `x` with a [`SpecCircuit`] of minimal size.
+/// Fails with [`Error::Synthesis`] if the spec rejects `x`.
+pub fn prove_spec(spec: &PolynomialSpec, x: u64) -> Result<SpecProof, Error> {
+    let PolynomialResult::Ok(y) = spec.evaluate(x) else {
+        return Err(Error::Synthesis);
+    };
+    let circuit = SpecCircuit {
+        spec: spec.clone(),
+        x: Value::known(Fp::from(x)),
+    };
+    let public = vec![Fp::from(y)];
+
+    let k =
+        minimal_k(&circuit, &public).ok_or(Error::NotEnoughRowsAvailable { current_k: MAX_K })?;
+    let (params, pk) = setup(k, &circuit)?;
+    let proof = prove(&params, &pk, circuit, &public)?;
+
+    Ok(SpecProof {
+        params,
+        vk: pk.get_vk().clone(),
+        proof,
+        y,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial::{eval_generic, PolynomialCircuit};
+
+    #[test]
+    fn test_verify_strategies() {
+        let coeffs = [5, 1, 0, 1].map(Fp::from).to_vec();
+        let circuit = PolynomialCircuit {
+            coeffs,
+            x: Value::known(Fp::from(3)),
+            pad_to_k: None,
+        };
+        let public = vec![Fp::from(35)];
+
+        let (params, pk) = setup(5, &circuit).unwrap();
+        let proof = prove(&params, &pk, circuit, &public).unwrap();
+
+        for strategy in [VerifyStrategy::Single, VerifyStrategy::Accumulator] {
+            verify_with(&params, pk.get_vk(), &proof, &public, strategy).unwrap();
+            assert!(verify_with(&params, pk.get_vk(), &proof, &[Fp::from(36)], strategy).is_err());
+        }
+    }
+
+    // Proof size only depends on k since the number of columns is fixed,
+    // so it can only grow with the degree. See `examples/proof_size.rs` for the data.
+    #[test]
+    fn test_proof_size_by_degree() {
+        let x = Fp::from(3);
+        let mut last_size = 0;
+
+        for degree in 1..=10 {
+            let coeffs = vec![Fp::one(); degree + 1];
+            let public = vec![eval_generic(&coeffs, x)];
+            let circuit = PolynomialCircuit {
+                coeffs,
+                x: Value::known(x),
+                pad_to_k: None,
+            };
+
+            let k = minimal_k(&circuit, &public).unwrap();
+            let (params, pk) = setup(k, &circuit).unwrap();
+            let proof = prove(&params, &pk, circuit, &public).unwrap();
+            verify(&params, pk.get_vk(), &proof, &public).unwrap();
+
+            assert!(
+                proof.len() >= last_size,
+                "proof size decreased at degree {}",
+                degree
+            );
+            last_size = proof.len();
+        }
+    }
+
+    #[test]
+    fn test_prove_spec() {
+        // shared with the zkVM backend, which must commit the same y
+        let spec: PolynomialSpec =
+            toml::from_str(include_str!("../../risczero/core/example_spec.toml")).unwrap();
+
+        let spec_proof = prove_spec(&spec, 10).unwrap();
+        assert_eq!(PolynomialResult::Ok(spec_proof.y), spec.evaluate(10));
+        assert_eq!(spec_proof.y, 15);
+        let public = vec![Fp::from(spec_proof.y)];
+        verify(
+            &spec_proof.params,
+            &spec_proof.vk,
+            &spec_proof.proof,
+            &public,
+        )
+        .unwrap();
+
+        assert!(matches!(prove_spec(&spec, 101), Err(Error::Synthesis)));
+    }
+}
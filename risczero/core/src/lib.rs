@@ -0,0 +1,235 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// The largest `x` the guest accepts.
+pub const MAX_X: u64 = 100;
+
+/// Compute the polynomial y = x^3 + x + 5, returning `None` on overflow.
+pub fn evaluate(x: u64) -> Option<u64> {
+    x.checked_mul(x)
+        .and_then(|x2| x2.checked_mul(x))
+        .and_then(|x3| x3.checked_add(x))
+        .and_then(|x3| x3.checked_add(5))
+}
+
+/// Compute the polynomial y = x^3 + x + 5, clamping to `u64::MAX` instead of
+/// overflowing. The flag tells whether any step saturated.
+pub fn evaluate_saturating(x: u64) -> (u64, bool) {
+    let y = x
+        .saturating_mul(x)
+        .saturating_mul(x)
+        .saturating_add(x)
+        .saturating_add(5);
+
+    (y, evaluate(x).is_none())
+}
+
+/// Compute sum(x_i^3 + x_i + 5), returning `None` on overflow.
+pub fn evaluate_sum(xs: &[u64]) -> Option<u64> {
+    xs.iter()
+        .try_fold(0u64, |sum, &x| evaluate(x).and_then(|y| sum.checked_add(y)))
+}
+
+/// Compute the bivariate polynomial z = x^3 + y + 5, returning `None` on overflow.
+pub fn evaluate_bivariate(x: u64, y: u64) -> Option<u64> {
+    x.checked_mul(x)
+        .and_then(|x2| x2.checked_mul(x))
+        .and_then(|x3| x3.checked_add(y))
+        .and_then(|x3| x3.checked_add(5))
+}
+
+/// Check whether a * x^2 + b * x + c == 0, returning `None` on overflow.
+pub fn is_quadratic_root(a: i64, b: i64, c: i64, x: i64) -> Option<bool> {
+    let (a, b, c, x) = (a as i128, b as i128, c as i128, x as i128);
+    let value = a
+        .checked_mul(x)?
+        .checked_mul(x)?
+        .checked_add(b.checked_mul(x)?)?
+        .checked_add(c)?;
+
+    Some(value == 0)
+}
+
+/// Compute coeffs[0] + coeffs[1] * x + ... + coeffs[n] * x^n, returning `None`
+/// on overflow.
+pub fn evaluate_coeffs(coeffs: &[u64], x: u64) -> Option<u64> {
+    coeffs
+        .iter()
+        .rev()
+        .try_fold(0u64, |acc, &c| acc.checked_mul(x)?.checked_add(c))
+}
+
+/// A polynomial shared by the zkVM and the Halo2 backends, usually loaded
+/// from a TOML file:
+///
+/// ```toml
+/// coeffs = [5, 1, 0, 1]
+/// bound = 100
+/// modulus = 1000
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolynomialSpec {
+    /// Coefficients, lowest degree first.
+    pub coeffs: Vec<u64>,
+    /// The largest `x` accepted, if any.
+    #[serde(default)]
+    pub bound: Option<u64>,
+    /// If set, y is reported modulo this value.
+    #[serde(default)]
+    pub modulus: Option<u64>,
+}
+
+impl PolynomialSpec {
+    /// Evaluates the polynomial for `x`. Like [`PolynomialResult::evaluate`],
+    /// overflow is reported before the bound, and it is checked on the
+    /// unreduced value.
+    pub fn evaluate(&self, x: u64) -> PolynomialResult {
+        let Some(y) = evaluate_coeffs(&self.coeffs, x) else {
+            return PolynomialResult::Overflow;
+        };
+        if self.bound.is_some_and(|bound| x > bound) {
+            return PolynomialResult::TooLarge;
+        }
+
+        match self.modulus {
+            Some(0) => PolynomialResult::InvalidInput,
+            Some(modulus) => PolynomialResult::Ok(y % modulus),
+            None => PolynomialResult::Ok(y),
+        }
+    }
+}
+
+/// Defines a guest `main` that reads x and commits the `PolynomialResult` of a
+/// fixed polynomial, with coefficients listed lowest degree first. `name` is the
+/// function evaluating it, so `define_polynomial_guest!(cubic, [5, 1, 0, 1])`
+/// proves y = x^3 + x + 5. The guest crate must depend on `risc0-zkvm`.
+#[macro_export]
+macro_rules! define_polynomial_guest {
+    ($name:ident, [$($coeff:expr),+ $(,)?]) => {
+        fn $name(x: u64) -> $crate::PolynomialResult {
+            match $crate::evaluate_coeffs(&[$($coeff),+], x) {
+                Some(y) => $crate::PolynomialResult::Ok(y),
+                None => $crate::PolynomialResult::Overflow,
+            }
+        }
+
+        ::risc0_zkvm::guest::entry!(main);
+
+        fn main() {
+            let x: u64 = ::risc0_zkvm::guest::env::read();
+            ::risc0_zkvm::guest::env::commit(&$name(x));
+        }
+    };
+}
+
+/// The status committed to the journal by the guest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolynomialResult {
+    /// y = x^3 + x + 5
+    Ok(u64),
+    /// x is larger than `MAX_X`.
+    TooLarge,
+    /// y does not fit in a u64.
+    Overflow,
+    /// The input could not be parsed as x.
+    InvalidInput,
+}
+
+impl PolynomialResult {
+    /// Evaluate the polynomial for `x`. Overflow is reported before the
+    /// input bound, so the caller can tell the two apart.
+    pub fn evaluate(x: u64) -> Self {
+        match evaluate(x) {
+            None => Self::Overflow,
+            Some(_) if x > MAX_X => Self::TooLarge,
+            Some(y) => Self::Ok(y),
+        }
+    }
+
+    /// Parse x from exactly 8 little-endian bytes and evaluate the polynomial.
+    pub fn from_bytes(input: &[u8]) -> Self {
+        match <[u8; 8]>::try_from(input) {
+            Ok(bytes) => Self::evaluate(u64::from_le_bytes(bytes)),
+            Err(_) => Self::InvalidInput,
+        }
+    }
+}
+
+/// The longest program accepted by [`run_program`].
+pub const MAX_PROGRAM_LEN: usize = 64;
+
+/// The deepest stack allowed by [`run_program`].
+pub const MAX_STACK_DEPTH: usize = 16;
+
+/// An instruction of the stack-based polynomial bytecode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Op {
+    /// Push the input x.
+    PushX,
+    /// Push a constant.
+    PushConst(u64),
+    /// Pop two values and push their product.
+    Mul,
+    /// Pop two values and push their sum.
+    Add,
+}
+
+/// Why a program could not be evaluated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProgramError {
+    /// The program has more than `MAX_PROGRAM_LEN` instructions.
+    TooLong,
+    /// The stack would grow beyond `MAX_STACK_DEPTH`.
+    StackOverflow,
+    /// An instruction needs more values than the stack holds.
+    StackUnderflow,
+    /// An intermediate value does not fit in a u64.
+    Overflow,
+    /// The program does not leave exactly one value on the stack.
+    InvalidResult,
+}
+
+/// Evaluate a program for `x`.
+pub fn run_program(ops: &[Op], x: u64) -> Result<u64, ProgramError> {
+    if ops.len() > MAX_PROGRAM_LEN {
+        return Err(ProgramError::TooLong);
+    }
+
+    let mut stack = [0u64; MAX_STACK_DEPTH];
+    let mut depth = 0;
+    for op in ops {
+        match op {
+            Op::PushX | Op::PushConst(_) => {
+                if depth == MAX_STACK_DEPTH {
+                    return Err(ProgramError::StackOverflow);
+                }
+                stack[depth] = match op {
+                    Op::PushConst(c) => *c,
+                    _ => x,
+                };
+                depth += 1;
+            }
+            Op::Mul | Op::Add => {
+                if depth < 2 {
+                    return Err(ProgramError::StackUnderflow);
+                }
+                let (a, b) = (stack[depth - 2], stack[depth - 1]);
+                let value = match op {
+                    Op::Mul => a.checked_mul(b),
+                    _ => a.checked_add(b),
+                };
+                stack[depth - 2] = value.ok_or(ProgramError::Overflow)?;
+                depth -= 1;
+            }
+        }
+    }
+
+    match depth {
+        1 => Ok(stack[0]),
+        _ => Err(ProgramError::InvalidResult),
+    }
+}
@@ -0,0 +1,132 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use methods::POLYNOMIAL_ID;
+use risc0_zkvm::sha::Digest;
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+
+use crate::{image_id_hex, polynomial, verify_polynomial, VerifyError};
+
+/// A self-contained record of a proven evaluation, without the seal. It names
+/// the guest and the committed journal, so it can be checked against a receipt
+/// kept elsewhere.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attestation {
+    pub image_id_hex: String,
+    pub output: u64,
+    /// The journal bytes as committed by the guest, in hex.
+    pub journal_hex: String,
+    /// Seconds since the Unix epoch when the proof was produced.
+    pub timestamp: u64,
+}
+
+impl Attestation {
+    /// Serializes the attestation as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        // all fields are strings and integers, so serialization cannot fail
+        serde_json::to_string_pretty(self).expect("attestation is serializable")
+    }
+}
+
+// Prove the polynomial and return an attestation of the result
+pub fn attestation(x: u64) -> Attestation {
+    let (receipt, output) = polynomial(x);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_secs();
+
+    Attestation {
+        image_id_hex: image_id_hex(POLYNOMIAL_ID),
+        output,
+        journal_hex: receipt
+            .journal
+            .bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect(),
+        timestamp,
+    }
+}
+
+/// A receipt of the polynomial guest, signed by the party that produced it.
+pub struct SignedProof {
+    pub receipt: Receipt,
+    pub output: u64,
+    /// ed25519 signature over the image id followed by the journal bytes
+    pub signature: Signature,
+}
+
+impl SignedProof {
+    /// Checks the signature against `key`, then the receipt itself, and
+    /// returns the verified output.
+    pub fn verify(&self, key: &VerifyingKey) -> Result<u64, VerifyError> {
+        key.verify(&signed_message(&self.receipt), &self.signature)
+            .map_err(VerifyError::Signature)?;
+
+        verify_polynomial(&self.receipt)
+    }
+}
+
+// The signature binds the guest build and what it committed
+fn signed_message(receipt: &Receipt) -> Vec<u8> {
+    let mut message = Digest::from(POLYNOMIAL_ID).as_bytes().to_vec();
+    message.extend_from_slice(&receipt.journal.bytes);
+    message
+}
+
+// Prove the polynomial and sign the result with the given key
+pub fn attest_signed(x: u64, signing_key: &SigningKey) -> SignedProof {
+    let (receipt, output) = polynomial(x);
+    let signature = signing_key.sign(&signed_message(&receipt));
+
+    SignedProof {
+        receipt,
+        output,
+        signature,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PolynomialResult;
+    use risc0_zkvm::Journal;
+
+    #[test]
+    fn test_attestation() {
+        let attestation = attestation(3);
+        assert_eq!(attestation.output, 35);
+        assert_eq!(attestation.image_id_hex, image_id_hex(POLYNOMIAL_ID));
+        assert!(attestation.timestamp > 0);
+
+        // the journal is an even number of hex digits and decodes to the output
+        let journal = &attestation.journal_hex;
+        assert_eq!(journal.len() % 2, 0);
+        let bytes: Vec<u8> = (0..journal.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&journal[i..i + 2], 16).unwrap())
+            .collect();
+        let output: PolynomialResult = Journal::new(bytes).decode().unwrap();
+        assert_eq!(output, PolynomialResult::Ok(35));
+
+        let json = attestation.to_json();
+        let parsed: Attestation = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, attestation);
+    }
+
+    #[test]
+    fn test_attest_signed() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let signed = attest_signed(3, &signing_key);
+        assert_eq!(signed.output, 35);
+        assert_eq!(signed.verify(&signing_key.verifying_key()).unwrap(), 35);
+
+        let other_key = SigningKey::from_bytes(&[8; 32]).verifying_key();
+        assert!(matches!(
+            signed.verify(&other_key),
+            Err(VerifyError::Signature(_))
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::{Receiver, Sender};
+
+use risc0_zkvm::Receipt;
+
+use crate::{polynomial_checked, PolynomialError};
+
+/// An input of a batch paired with the outcome of proving it.
+pub type BatchEntry = (u64, Result<(Receipt, u64), PolynomialError>);
+
+/// A line of a batch file paired with the outcome of proving it. The input is
+/// `None` for a line that is not a u64, whose outcome is the parse error.
+pub type FileEntry = (Option<u64>, Result<(Receipt, u64), PolynomialError>);
+
+// Prove every x in a file with one input per line. Blank lines are skipped and a
+// malformed line becomes a `PolynomialError::Parse` entry without an x, so one
+// bad line does not abort the batch.
+pub fn prove_from_file(path: &Path) -> Vec<FileEntry> {
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", path.display(), err));
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| match line.trim().parse::<u64>() {
+            Ok(x) => (Some(x), polynomial_checked(x)),
+            Err(source) => (
+                None,
+                Err(PolynomialError::Parse {
+                    line: i + 1,
+                    input: line.to_string(),
+                    source,
+                }),
+            ),
+        })
+        .collect()
+}
+
+// Prove each x received on `rx` and send it back with its outcome on `tx`, in
+// the order received. Runs until every sender of `rx` is dropped, or until the
+// receiver of `tx` is, so it can back a long-running worker thread.
+pub fn prove_channel(rx: Receiver<u64>, tx: Sender<BatchEntry>) {
+    for x in rx {
+        if tx.send((x, polynomial_checked(x))).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prove_from_file() {
+        let path = std::env::temp_dir().join(format!("host-batch-{}.txt", std::process::id()));
+        fs::write(&path, "3\n1\nnot a number\n").unwrap();
+
+        let results = prove_from_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], (Some(3), Ok((_, 35)))));
+        assert!(matches!(results[1], (Some(1), Ok((_, 7)))));
+        assert!(matches!(
+            results[2],
+            (None, Err(PolynomialError::Parse { line: 3, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_prove_channel() {
+        let (input_tx, input_rx) = std::sync::mpsc::channel();
+        let (result_tx, result_rx) = std::sync::mpsc::channel();
+        let worker = std::thread::spawn(move || prove_channel(input_rx, result_tx));
+
+        for x in [3, 101, 4] {
+            input_tx.send(x).unwrap();
+        }
+        drop(input_tx);
+
+        let results: Vec<BatchEntry> = result_rx.iter().collect();
+        worker.join().unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], (3, Ok((_, 35)))));
+        assert!(matches!(
+            results[1],
+            (101, Err(PolynomialError::TooLarge { x: 101, .. }))
+        ));
+        assert!(matches!(results[2], (4, Ok((_, 73)))));
+    }
+}
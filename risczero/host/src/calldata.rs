@@ -0,0 +1,86 @@
+use methods::POLYNOMIAL_ID;
+use risc0_zkvm::sha::{Digest, Digestible};
+use risc0_zkvm::{default_prover, ProverOpts};
+use sha3::{Digest as _, Keccak256};
+
+use crate::polynomial;
+
+/// The verifier contract entry point the calldata is encoded for.
+pub const VERIFY_SIGNATURE: &str = "verify(bytes,bytes32,bytes32)";
+
+// Prove the polynomial, compress the receipt to Groth16 and ABI-encode a call to
+// `verify(seal, imageId, journalDigest)` on a RISC Zero verifier contract. The
+// seal is prefixed with the first 4 bytes of the verifier parameters digest, the
+// selector a verifier router dispatches on. The contract only takes the digest
+// of the journal, so the journal itself must be sent alongside.
+pub fn groth16_calldata(x: u64) -> Vec<u8> {
+    let (receipt, _) = polynomial(x);
+    let receipt = default_prover()
+        .compress(&ProverOpts::groth16(), &receipt)
+        .unwrap();
+    let groth16 = receipt.inner.groth16().unwrap();
+
+    let mut seal = groth16.verifier_parameters.as_bytes()[..4].to_vec();
+    seal.extend_from_slice(&groth16.seal);
+
+    abi_encode_verify(&seal, Digest::from(POLYNOMIAL_ID), receipt.journal.digest())
+}
+
+fn abi_encode_verify(seal: &[u8], image_id: Digest, journal_digest: Digest) -> Vec<u8> {
+    let mut calldata = Keccak256::digest(VERIFY_SIGNATURE.as_bytes())[..4].to_vec();
+    // head: offset of the dynamic seal, then the two static words
+    calldata.extend_from_slice(&abi_word(3 * 32));
+    calldata.extend_from_slice(image_id.as_bytes());
+    calldata.extend_from_slice(journal_digest.as_bytes());
+    // tail: the seal length, then the seal padded to whole words
+    calldata.extend_from_slice(&abi_word(seal.len()));
+    calldata.extend_from_slice(seal);
+    calldata.resize(calldata.len() + (32 - seal.len() % 32) % 32, 0);
+
+    calldata
+}
+
+// A uint256 ABI word
+fn abi_word(value: usize) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abi_encode_verify() {
+        let seal = [0xab; 36];
+        let calldata = abi_encode_verify(&seal, Digest::ZERO, Digest::ZERO);
+
+        // selector, three head words, the length and two words of seal
+        assert_eq!(calldata.len(), 4 + 3 * 32 + 32 + 64);
+        assert_eq!(calldata[4 + 31], 0x60);
+        assert_eq!(calldata[4 + 3 * 32 + 31], 36);
+        assert_eq!(&calldata[4 + 4 * 32..][..36], &seal);
+        assert!(calldata[4 + 4 * 32 + 36..].iter().all(|b| *b == 0));
+    }
+
+    #[cfg(feature = "groth16")]
+    #[test]
+    fn test_groth16_calldata() {
+        use polynomial_core::PolynomialResult;
+        use risc0_zkvm::Journal;
+
+        let calldata = groth16_calldata(3);
+        // a 4 byte selector and a 256 byte Groth16 seal pad to 9 words
+        assert_eq!(calldata.len(), 4 + 3 * 32 + 32 + 9 * 32);
+        assert_eq!(
+            &calldata[4 + 32..][..32],
+            Digest::from(POLYNOMIAL_ID).as_bytes()
+        );
+
+        // the journal digest is that of a journal committing 35
+        let words = risc0_zkvm::serde::to_vec(&PolynomialResult::Ok(35)).unwrap();
+        let journal = Journal::new(words.iter().flat_map(|w| w.to_le_bytes()).collect());
+        assert_eq!(&calldata[4 + 64..][..32], journal.digest().as_bytes());
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use methods::POLYNOMIAL_ELF;
+use risc0_zkvm::{
+    ApiClient, Asset, AssetRequest, ExecutorEnv, InnerReceipt, ProverOpts, Receipt, ReceiptClaim,
+    SuccinctReceipt,
+};
+
+use crate::{check_result, PolynomialError};
+
+// Compute the polynomial in zkVM, checking `cancel` after each segment is
+// executed and before each one is proven. The segments are driven one by one
+// through r0vm, lifted and joined into a succinct receipt.
+pub fn polynomial_cancellable(
+    x: u64,
+    cancel: Arc<AtomicBool>,
+) -> Result<(Receipt, u64), PolynomialError> {
+    let cancelled = || cancel.load(Ordering::Relaxed);
+    let client = ApiClient::from_env().map_err(PolynomialError::Prove)?;
+    let env = ExecutorEnv::builder()
+        // send x to the guest
+        .write(&x)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut segments = Vec::new();
+    let session = client.execute(
+        &env,
+        Asset::Inline(POLYNOMIAL_ELF.to_vec().into()),
+        AssetRequest::Inline,
+        |_, segment| {
+            if cancelled() {
+                bail!("cancelled");
+            }
+            segments.push(segment);
+            Ok(())
+        },
+    );
+    if cancelled() {
+        return Err(PolynomialError::Cancelled);
+    }
+    let session = session.map_err(PolynomialError::Prove)?;
+
+    let opts = ProverOpts::default();
+    let mut joined: Option<SuccinctReceipt<ReceiptClaim>> = None;
+    for segment in segments {
+        if cancelled() {
+            return Err(PolynomialError::Cancelled);
+        }
+
+        let lifted = client
+            .prove_segment(&opts, segment, AssetRequest::Inline)
+            .and_then(|receipt| client.lift(&opts, receipt.try_into()?, AssetRequest::Inline))
+            .map_err(PolynomialError::Prove)?;
+
+        joined = Some(match joined {
+            None => lifted,
+            Some(left) => client
+                .join(&opts, asset(&left)?, asset(&lifted)?, AssetRequest::Inline)
+                .map_err(PolynomialError::Prove)?,
+        });
+    }
+
+    let joined = joined.ok_or(PolynomialError::Prove(anyhow!("the session is empty")))?;
+    let receipt = Receipt::new(InnerReceipt::Succinct(joined), session.journal.bytes);
+
+    check_result(receipt, x)
+}
+
+pub(crate) fn asset(receipt: &SuccinctReceipt<ReceiptClaim>) -> Result<Asset, PolynomialError> {
+    let bytes = bincode::serialize(receipt).map_err(|err| PolynomialError::Prove(err.into()))?;
+    Ok(Asset::Inline(bytes.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use methods::POLYNOMIAL_ID;
+
+    #[test]
+    fn test_polynomial_cancellable() {
+        let (receipt, output) = polynomial_cancellable(3, Arc::default()).unwrap();
+        assert_eq!(output, 35);
+        receipt.verify(POLYNOMIAL_ID).expect("Verification failed");
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let cancel = cancel.clone();
+            std::thread::spawn(move || polynomial_cancellable(3, cancel))
+        };
+        cancel.store(true, Ordering::Relaxed);
+
+        assert!(matches!(
+            handle.join().unwrap(),
+            Err(PolynomialError::Cancelled)
+        ));
+    }
+}
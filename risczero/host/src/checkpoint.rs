@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use methods::{POLYNOMIAL_ELF, POLYNOMIAL_ID};
+use risc0_zkvm::{
+    ApiClient, Asset, AssetRequest, ExecutorEnv, InnerReceipt, ProverOpts, Receipt, ReceiptClaim,
+    SuccinctReceipt,
+};
+
+use crate::cancel::asset;
+use crate::{check_result, image_id_hex, PolynomialError};
+
+// Compute the polynomial in zkVM, saving the lifted receipt of each segment in
+// `checkpoint_dir`. Segments with a saved receipt are not proven again, so an
+// interrupted run resumes where it stopped. Execution is deterministic, so the
+// segments of a rerun match the saved ones as long as the guest is unchanged;
+// the checkpoints are keyed by the image id, so a rebuilt guest proves again.
+pub fn prove_checkpointed(
+    x: u64,
+    checkpoint_dir: &Path,
+) -> Result<(Receipt, u64), PolynomialError> {
+    let client = ApiClient::from_env().map_err(PolynomialError::Prove)?;
+    let env = ExecutorEnv::builder()
+        // send x to the guest
+        .write(&x)
+        .unwrap()
+        .build()
+        .unwrap();
+    fs::create_dir_all(checkpoint_dir).map_err(|err| PolynomialError::Prove(err.into()))?;
+
+    let mut segments = Vec::new();
+    let session = client
+        .execute(
+            &env,
+            Asset::Inline(POLYNOMIAL_ELF.to_vec().into()),
+            AssetRequest::Inline,
+            |info, segment| {
+                segments.push((info.po2, segment));
+                Ok(())
+            },
+        )
+        .map_err(PolynomialError::Prove)?;
+
+    let opts = ProverOpts::default();
+    let mut joined: Option<SuccinctReceipt<ReceiptClaim>> = None;
+    for (index, (po2, segment)) in segments.into_iter().enumerate() {
+        let path = checkpoint_path(checkpoint_dir, POLYNOMIAL_ID, x, po2, index);
+        let lifted = match load_checkpoint(&path) {
+            Some(lifted) => lifted,
+            None => {
+                let lifted = client
+                    .prove_segment(&opts, segment, AssetRequest::Inline)
+                    .and_then(|receipt| {
+                        client.lift(&opts, receipt.try_into()?, AssetRequest::Inline)
+                    })
+                    .map_err(PolynomialError::Prove)?;
+                save_checkpoint(&path, &lifted)?;
+                lifted
+            }
+        };
+
+        joined = Some(match joined {
+            None => lifted,
+            Some(left) => client
+                .join(&opts, asset(&left)?, asset(&lifted)?, AssetRequest::Inline)
+                .map_err(PolynomialError::Prove)?,
+        });
+    }
+
+    let joined = joined.ok_or(PolynomialError::Prove(anyhow!("the session is empty")))?;
+    let receipt = Receipt::new(InnerReceipt::Succinct(joined), session.journal.bytes);
+
+    check_result(receipt, x)
+}
+
+// A segment is only reused for the same image, input and segment size
+fn checkpoint_path(dir: &Path, image_id: [u32; 8], x: u64, po2: u32, index: usize) -> PathBuf {
+    dir.join(format!(
+        "{}-x{}-po2_{}-segment{}.bin",
+        image_id_hex(image_id),
+        x,
+        po2,
+        index
+    ))
+}
+
+// A missing or unreadable checkpoint is proven again
+fn load_checkpoint(path: &Path) -> Option<SuccinctReceipt<ReceiptClaim>> {
+    let bytes = fs::read(path).ok()?;
+    bincode::deserialize(&bytes).ok()
+}
+
+fn save_checkpoint(
+    path: &Path,
+    receipt: &SuccinctReceipt<ReceiptClaim>,
+) -> Result<(), PolynomialError> {
+    let bytes = bincode::serialize(receipt).map_err(|err| PolynomialError::Prove(err.into()))?;
+    // write to a temporary file first, so an interruption never leaves a
+    // truncated checkpoint behind
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, bytes)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|err| PolynomialError::Prove(err.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::same_statement;
+
+    #[test]
+    fn test_prove_checkpointed() {
+        let dir = std::env::temp_dir().join(format!("host_checkpoint_{}", std::process::id()));
+        let (uninterrupted, output) = prove_checkpointed(3, &dir).unwrap();
+        assert_eq!(output, 35);
+        uninterrupted
+            .verify(POLYNOMIAL_ID)
+            .expect("Verification failed");
+
+        // an interruption before the last segment was saved
+        let saved = fs::read_dir(&dir).unwrap().count();
+        assert!(saved > 0);
+        let last = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| {
+                path.to_string_lossy()
+                    .ends_with(&format!("-segment{}.bin", saved - 1))
+            })
+            .unwrap();
+        fs::remove_file(last).unwrap();
+
+        let (resumed, output) = prove_checkpointed(3, &dir).unwrap();
+        assert_eq!(output, 35);
+        resumed.verify(POLYNOMIAL_ID).expect("Verification failed");
+        assert!(same_statement(&resumed, &uninterrupted));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), saved);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_path() {
+        let dir = Path::new("checkpoints");
+        let path = checkpoint_path(dir, POLYNOMIAL_ID, 3, 20, 0);
+
+        // a rebuilt guest or another segment size does not reuse the checkpoint
+        let mut rebuilt = POLYNOMIAL_ID;
+        rebuilt[0] ^= 1;
+        assert_ne!(path, checkpoint_path(dir, rebuilt, 3, 20, 0));
+        assert_ne!(path, checkpoint_path(dir, POLYNOMIAL_ID, 3, 19, 0));
+        assert_ne!(path, checkpoint_path(dir, POLYNOMIAL_ID, 3, 20, 1));
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::Instant;
+
+use crate::{polynomial_with_stats, verify_polynomial};
+
+// Prove and verify the polynomial for x, returning a report of the run
+pub fn demo(x: u64) -> String {
+    let start = Instant::now();
+    let (receipt, output, stats) = polynomial_with_stats(x).unwrap_or_else(|err| panic!("{}", err));
+    let prove_time = start.elapsed();
+
+    let start = Instant::now();
+    verify_polynomial(&receipt).unwrap_or_else(|err| panic!("{}", err));
+    let verify_time = start.elapsed();
+
+    format!(
+        "x = {}\ny = {}\nprove time: {:?}\nverify time: {:?}\nsegments: {}\n",
+        x, output, prove_time, verify_time, stats.segments
+    )
+}
+
+// Prove the polynomial for x and return the proving throughput in cycles per
+// second. The cycles include paging and padding, as those are proven too.
+pub fn throughput(x: u64) -> f64 {
+    let start = Instant::now();
+    let (_, _, stats) = polynomial_with_stats(x).unwrap_or_else(|err| panic!("{}", err));
+    let prove_time = start.elapsed();
+
+    stats.cycles as f64 / prove_time.as_secs_f64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo() {
+        let report = demo(3);
+        assert!(report.contains("x = 3"));
+        assert!(report.contains("y = 35"));
+        assert!(report.contains("prove time: "));
+        assert!(!report.contains("prove time: 0ns"));
+    }
+
+    #[test]
+    fn test_throughput() {
+        let first = throughput(3);
+        let second = throughput(3);
+        assert!(first > 0.0 && second > 0.0);
+
+        // the same work on the same machine, allowing for noise from other tests
+        let ratio = first / second;
+        assert!(
+            (0.25..4.0).contains(&ratio),
+            "{} vs {} cycles/s",
+            first,
+            second
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use methods::POLYNOMIAL_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv, ExitCode, TraceEvent};
+
+/// Statistics about executing the guest, without proving it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    /// User cycles across all segments
+    pub cycles: u64,
+    /// Instructions executed by the guest. Trace events are only delivered by
+    /// an in-process executor (the `prove` feature), otherwise this is 0.
+    pub instructions: u64,
+    pub segments: usize,
+    pub exit_code: ExitCode,
+}
+
+// Execute the polynomial guest for x and summarize the session
+pub fn polynomial_trace(x: u64) -> ExecutionTrace {
+    let mut instructions = 0;
+    let env = ExecutorEnv::builder()
+        // send x to the guest
+        .write(&x)
+        .unwrap()
+        .trace_callback(|event| {
+            if let TraceEvent::InstructionStart { .. } = event {
+                instructions += 1;
+            }
+            Ok(())
+        })
+        .build()
+        .unwrap();
+
+    let session = default_executor().execute(env, POLYNOMIAL_ELF).unwrap();
+
+    ExecutionTrace {
+        cycles: session.cycles(),
+        instructions,
+        segments: session.segments.len(),
+        exit_code: session.exit_code,
+    }
+}
+
+// Execute the polynomial guest for x and return the user cycles of each
+// segment, in order. Segments are padded to a power of two when proven, which
+// these counts leave out.
+pub fn segment_cycles(x: u64) -> Vec<u64> {
+    let env = ExecutorEnv::builder()
+        // send x to the guest
+        .write(&x)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let session = default_executor().execute(env, POLYNOMIAL_ELF).unwrap();
+
+    session
+        .segments
+        .iter()
+        .map(|segment| segment.cycles as u64)
+        .collect()
+}
+
+// Estimate the cost of proving the polynomial for x on a hosted prover billing
+// per million cycles. Segments are padded to a power of two, so the estimate
+// uses the padded total rather than the user cycles. This guest does the same
+// few operations for every x, so in practice the estimate only depends on the
+// price.
+pub fn estimate_cost_usd(x: u64, price_per_mcycle: f64) -> f64 {
+    let env = ExecutorEnv::builder()
+        // send x to the guest
+        .write(&x)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let session = default_executor().execute(env, POLYNOMIAL_ELF).unwrap();
+    let total_cycles: u64 = session.segments.iter().map(|s| 1u64 << s.po2).sum();
+
+    total_cycles as f64 / 1_000_000.0 * price_per_mcycle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polynomial_core::MAX_X;
+
+    #[test]
+    fn test_polynomial_trace() {
+        let trace = polynomial_trace(3);
+        assert_eq!(trace.exit_code, ExitCode::Halted(0));
+        assert!(trace.cycles > 0);
+        assert!(trace.segments > 0);
+        if cfg!(feature = "prove") {
+            assert!(trace.instructions > 0);
+        }
+    }
+
+    #[test]
+    fn test_segment_cycles() {
+        let cycles = segment_cycles(3);
+        assert!(!cycles.is_empty());
+        assert_eq!(cycles.iter().sum::<u64>(), polynomial_trace(3).cycles);
+    }
+
+    #[test]
+    fn test_estimate_cost_usd() {
+        assert_eq!(estimate_cost_usd(3, 0.0), 0.0);
+
+        let cost = estimate_cost_usd(3, 1.0);
+        assert!(cost > 0.0);
+        assert_eq!(estimate_cost_usd(3, 2.0), 2.0 * cost);
+        // the work does not depend on x, both pad to the same segments
+        assert_eq!(estimate_cost_usd(MAX_X, 1.0), cost);
+    }
+}
@@ -1,8 +1,227 @@
+use std::fmt;
+use std::num::ParseIntError;
+
 use methods::POLYNOMIAL_ELF;
-use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
+use risc0_zkvm::sha::{Digest, Impl, Sha256};
+use risc0_zkvm::{default_prover, ExecutorEnv, ExitCode, Prover, Receipt, SessionStats};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+mod attestation;
+mod batch;
+mod calldata;
+mod cancel;
+mod checkpoint;
+mod demo;
+mod execution;
+mod memory;
+mod proof_log;
+mod prover;
+mod receipt;
+mod spec;
+#[cfg(feature = "otel")]
+mod telemetry;
+mod variants;
+mod verify;
+
+pub use attestation::{attest_signed, attestation, Attestation, SignedProof};
+pub use batch::{prove_channel, prove_from_file, BatchEntry, FileEntry};
+pub use calldata::{groth16_calldata, VERIFY_SIGNATURE};
+pub use cancel::polynomial_cancellable;
+pub use checkpoint::prove_checkpointed;
+pub use demo::{demo, throughput};
+pub use execution::{estimate_cost_usd, polynomial_trace, segment_cycles, ExecutionTrace};
+pub use memory::polynomial_with_memory;
+pub use polynomial_core::{Op, PolynomialResult, PolynomialSpec, ProgramError};
+pub use proof_log::{log_proof, read_proof_log, ProofLogEntry};
+pub use prover::{PolynomialProverCtx, ProverKind};
+pub use receipt::{
+    fake_receipt, load_receipt_limited, peek_output, polynomial_compressed, receipt_kind,
+    seal_size, CompressionLevel, DecodeError, ReceiptKind,
+};
+pub use spec::{load_spec, polynomial_from_spec};
+#[cfg(feature = "otel")]
+pub use telemetry::polynomial_with_spans;
+pub use variants::{
+    open_commit, polynomial_bivariate, polynomial_bytes, polynomial_commit, polynomial_compare,
+    polynomial_cubic, polynomial_digit_sum, polynomial_forward_diff, polynomial_nonce,
+    polynomial_program, polynomial_quadratic, polynomial_quadratic_root, polynomial_saturating,
+    polynomial_seeded, polynomial_sum, polynomial_threshold, polynomial_vec_bounded,
+};
+pub use verify::{
+    claim_digest, explain_id_mismatch, journal_digest, same_statement, verify_against,
+    verify_batch, verify_detailed, verify_polynomial, verify_timed, ClaimInfo, VerifyError,
+};
+
+/// Errors reported by [`polynomial_checked`].
+#[derive(Debug)]
+pub enum PolynomialError {
+    /// `x` is larger than the bound accepted by the guest.
+    TooLarge { x: u64, receipt: Box<Receipt> },
+    /// y = x^3 + x + 5 does not fit in a u64.
+    Overflow { x: u64, receipt: Box<Receipt> },
+    /// The guest could not parse its input.
+    InvalidInput { receipt: Box<Receipt> },
+    /// The prover failed to produce a receipt.
+    Prove(anyhow::Error),
+    /// The journal does not contain a `PolynomialResult`.
+    Decode(risc0_zkvm::serde::Error),
+    /// A line of a batch input is not a u64.
+    Parse {
+        line: usize,
+        input: String,
+        source: ParseIntError,
+    },
+    /// Proving was cancelled by the caller.
+    Cancelled,
+}
+
+impl fmt::Display for PolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { x, .. } => write!(f, "x = {} is too large", x),
+            Self::Overflow { x, .. } => write!(f, "x = {} is too large for u64", x),
+            Self::InvalidInput { .. } => write!(f, "the guest could not parse its input"),
+            Self::Prove(err) => write!(f, "failed to prove: {}", err),
+            Self::Decode(err) => write!(f, "failed to decode the journal: {}", err),
+            Self::Parse {
+                line,
+                input,
+                source,
+            } => write!(f, "line {}: invalid x {:?}: {}", line, input, source),
+            Self::Cancelled => write!(f, "proving was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for PolynomialError {}
+
+/// Statistics reported by the prover.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProveStats {
+    /// Number of segments in the proof
+    pub segments: usize,
+    /// Total cycles, including paging and padding
+    pub cycles: u64,
+    /// Cycles spent running the guest
+    pub user_cycles: u64,
+}
+
+impl From<&SessionStats> for ProveStats {
+    fn from(stats: &SessionStats) -> Self {
+        Self {
+            segments: stats.segments,
+            cycles: stats.total_cycles,
+            user_cycles: stats.user_cycles,
+        }
+    }
+}
+
+/// Formats an image id as the hex string used by RISC Zero tooling.
+pub fn image_id_hex(image_id: [u32; 8]) -> String {
+    Digest::from(image_id).to_string()
+}
 
 // Compute the polynomial y = x^3 + x + 5 in zkVM
 pub fn polynomial(x: u64) -> (Receipt, u64) {
+    let (receipt, output) = polynomial_checked(x).unwrap_or_else(|err| panic!("{}", err));
+
+    println!("I know that result is {}, and I can prove it!", output);
+
+    (receipt, output)
+}
+
+// Compute the polynomial in zkVM, surfacing the status committed by the guest
+pub fn polynomial_checked(x: u64) -> Result<(Receipt, u64), PolynomialError> {
+    prove(default_prover().as_ref(), x).map(|(receipt, output, _)| (receipt, output))
+}
+
+// Compute the polynomial in zkVM with an explicitly selected prover
+pub fn polynomial_with(kind: ProverKind, x: u64) -> Result<(Receipt, u64), PolynomialError> {
+    prove(kind.prover().as_ref(), x).map(|(receipt, output, _)| (receipt, output))
+}
+
+// Compute the polynomial in zkVM, also returning the prover statistics
+pub fn polynomial_with_stats(x: u64) -> Result<(Receipt, u64, ProveStats), PolynomialError> {
+    prove(default_prover().as_ref(), x)
+}
+
+// Compute the polynomial in zkVM, also returning sha256 of the guest ELF. The
+// image id is derived from the loaded program, the hash pins the exact binary
+// a verifier expects to have been proven.
+pub fn polynomial_with_elf_hash(x: u64) -> (Receipt, u64, [u8; 32]) {
+    let (receipt, output) = polynomial_checked(x).unwrap_or_else(|err| panic!("{}", err));
+
+    let elf_hash = Impl::hash_bytes(POLYNOMIAL_ELF)
+        .as_bytes()
+        .try_into()
+        .unwrap();
+
+    (receipt, output, elf_hash)
+}
+
+// Compute the polynomial in zkVM with segments of at most 2^po2 cycles. A
+// smaller po2 splits the execution into more segments that each need less
+// memory to prove.
+pub fn polynomial_seg_limit(x: u64, po2: u32) -> (Receipt, u64) {
+    let (receipt, output, _) = prove_env(default_prover().as_ref(), seg_limit_env(x, po2), x)
+        .unwrap_or_else(|err| panic!("{}", err));
+
+    (receipt, output)
+}
+
+fn seg_limit_env(x: u64, po2: u32) -> ExecutorEnv<'static> {
+    ExecutorEnv::builder()
+        // send x to the guest
+        .write(&x)
+        .unwrap()
+        .segment_limit_po2(po2)
+        .build()
+        .unwrap()
+}
+
+// Compute the polynomial in zkVM, also returning how the guest terminated
+pub fn polynomial_exit_code(x: u64) -> (Receipt, u64, ExitCode) {
+    let (receipt, output) = polynomial_checked(x).unwrap_or_else(|err| panic!("{}", err));
+
+    let exit_code = receipt
+        .claim()
+        .expect("The receipt does not contain a claim")
+        .as_value()
+        .expect("The receipt claim is pruned")
+        .exit_code;
+
+    (receipt, output, exit_code)
+}
+
+// Compute the polynomial in zkVM, also returning the lines logged by the guest
+pub fn polynomial_with_logs(x: u64) -> (Receipt, u64, Vec<String>) {
+    let mut stdout = Vec::new();
+    let env = ExecutorEnv::builder()
+        // send x to the guest
+        .write(&x)
+        .unwrap()
+        // env::log writes to the guest's stdout
+        .stdout(&mut stdout)
+        .build()
+        .unwrap();
+
+    let (receipt, output, _) =
+        prove_env(default_prover().as_ref(), env, x).unwrap_or_else(|err| panic!("{}", err));
+
+    // each line is prefixed with the cycle it was logged at, e.g. "R0VM[1234] "
+    let logs = String::from_utf8_lossy(&stdout)
+        .lines()
+        .map(|line| match line.split_once("] ") {
+            Some((prefix, msg)) if prefix.starts_with("R0VM[") => msg.to_string(),
+            _ => line.to_string(),
+        })
+        .collect();
+
+    (receipt, output, logs)
+}
+
+fn prove(prover: &dyn Prover, x: u64) -> Result<(Receipt, u64, ProveStats), PolynomialError> {
     let env = ExecutorEnv::builder()
         // send x to the guest
         .write(&x)
@@ -10,21 +229,74 @@ pub fn polynomial(x: u64) -> (Receipt, u64) {
         .build()
         .unwrap();
 
-    let prover = default_prover();
-    let receipt = prover.prove(env, POLYNOMIAL_ELF).unwrap().receipt;
+    prove_env(prover, env, x)
+}
+
+fn prove_env(
+    prover: &dyn Prover,
+    env: ExecutorEnv<'_>,
+    x: u64,
+) -> Result<(Receipt, u64, ProveStats), PolynomialError> {
+    let info = prover
+        .prove(env, POLYNOMIAL_ELF)
+        .map_err(PolynomialError::Prove)?;
+    let stats = ProveStats::from(&info.stats);
+
+    check_result(info.receipt, x).map(|(receipt, output)| (receipt, output, stats))
+}
+
+// Prove a guest and decode its journal, panicking on failure. Several inputs are
+// sent as a tuple, which serializes to the same words as writing each in turn.
+pub(crate) fn prove_guest<T: DeserializeOwned>(
+    elf: &[u8],
+    inputs: &impl Serialize,
+) -> (Receipt, T) {
+    let env = ExecutorEnv::builder()
+        .write(inputs)
+        .unwrap()
+        .build()
+        .unwrap();
 
-    let output: u64 = receipt.journal.decode().expect(
+    let receipt = default_prover().prove(env, elf).unwrap().receipt;
+    let output = receipt.journal.decode().expect(
         "Failed to decode the output from the journal. This is likely a bug in the guest program.",
     );
 
-    println!("I know that result is {}, and I can prove it!", output);
-
     (receipt, output)
 }
 
+// Map the status committed by the guest to the output or an error
+fn check_result(receipt: Receipt, x: u64) -> Result<(Receipt, u64), PolynomialError> {
+    let result: PolynomialResult = receipt.journal.decode().map_err(PolynomialError::Decode)?;
+
+    into_output(result, receipt, x)
+}
+
+fn into_output(
+    result: PolynomialResult,
+    receipt: Receipt,
+    x: u64,
+) -> Result<(Receipt, u64), PolynomialError> {
+    match result {
+        PolynomialResult::Ok(output) => Ok((receipt, output)),
+        PolynomialResult::TooLarge => Err(PolynomialError::TooLarge {
+            x,
+            receipt: Box::new(receipt),
+        }),
+        PolynomialResult::Overflow => Err(PolynomialError::Overflow {
+            x,
+            receipt: Box::new(receipt),
+        }),
+        PolynomialResult::InvalidInput => Err(PolynomialError::InvalidInput {
+            receipt: Box::new(receipt),
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use methods::POLYNOMIAL_ID;
 
     #[test]
     fn test_polynomial() {
@@ -36,4 +308,58 @@ mod tests {
             "The output is not correct. The polynomial is y = x^3 + x + 5"
         );
     }
+
+    #[test]
+    fn test_polynomial_overflow() {
+        match polynomial_checked(3_000_000) {
+            Err(PolynomialError::Overflow { x, receipt }) => {
+                assert_eq!(x, 3_000_000);
+                receipt.verify(POLYNOMIAL_ID).expect("Verification failed");
+            }
+            other => panic!("expected an overflow, got {:?}", other.map(|(_, y)| y)),
+        }
+    }
+
+    #[test]
+    fn test_polynomial_exit_code() {
+        let (_, output, exit_code) = polynomial_exit_code(3);
+        assert_eq!(output, 35);
+        assert_eq!(exit_code, ExitCode::Halted(0));
+    }
+
+    #[test]
+    fn test_polynomial_with_logs() {
+        let (_, output, logs) = polynomial_with_logs(3);
+        assert_eq!(output, 35);
+        assert!(logs.iter().any(|line| line.contains("x = 3")), "{:?}", logs);
+    }
+
+    #[test]
+    fn test_polynomial_with_elf_hash() {
+        let (receipt, output, hash) = polynomial_with_elf_hash(3);
+        assert_eq!(output, 35);
+        receipt.verify(POLYNOMIAL_ID).expect("Verification failed");
+
+        let (_, _, again) = polynomial_with_elf_hash(3);
+        assert_eq!(hash, again);
+        assert_eq!(&hash[..], Impl::hash_bytes(POLYNOMIAL_ELF).as_bytes());
+    }
+
+    #[test]
+    fn test_polynomial_seg_limit() {
+        let (receipt, output) = polynomial_seg_limit(3, 13);
+        assert_eq!(output, 35);
+        receipt.verify(POLYNOMIAL_ID).expect("Verification failed");
+
+        // 2^13 is the smallest segment the prover accepts
+        let (_, _, limited) =
+            prove_env(default_prover().as_ref(), seg_limit_env(3, 13), 3).unwrap();
+        let (_, _, default) = polynomial_with_stats(3).unwrap();
+        assert!(
+            limited.segments > default.segments,
+            "{} segments with the limit, {} without",
+            limited.segments,
+            default.segments
+        );
+    }
 }
@@ -1,7 +1,110 @@
-use host::polynomial;
+use std::{env, fs, process};
+
+use host::{image_id_hex, polynomial, polynomial_with_stats, verify_polynomial};
 use methods::POLYNOMIAL_ID;
+use serde::Serialize;
+
+const USAGE: &str = "usage: host [prove <x> [--json-meta <file>] [--format {dec,hex,bytes}]]";
+
+// How `prove` prints y
+#[derive(Clone, Copy, Default)]
+enum Format {
+    #[default]
+    Dec,
+    Hex,
+    // the journal as committed by the guest, little-endian words in hex
+    Bytes,
+}
+
+impl Format {
+    fn parse(arg: &str) -> Result<Self, String> {
+        match arg {
+            "dec" => Ok(Self::Dec),
+            "hex" => Ok(Self::Hex),
+            "bytes" => Ok(Self::Bytes),
+            _ => Err(format!("invalid format {:?}\n{}", arg, USAGE)),
+        }
+    }
+}
+
+// Machine-readable summary written by `prove --json-meta`
+#[derive(Serialize)]
+struct ProveMeta {
+    x: u64,
+    y: u64,
+    image_id: String,
+    segments: usize,
+    cycles: u64,
+}
+
+fn prove(args: &[String]) -> Result<(), String> {
+    let mut x = None;
+    let mut json_meta = None;
+    let mut format = Format::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json-meta" => json_meta = Some(args.next().ok_or(USAGE)?),
+            "--format" => format = Format::parse(args.next().ok_or(USAGE)?)?,
+            _ if x.is_none() => {
+                x = Some(
+                    arg.parse::<u64>()
+                        .map_err(|err| format!("invalid x {:?}: {}", arg, err))?,
+                )
+            }
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let x = x.ok_or(USAGE)?;
+
+    let (receipt, y, stats) = polynomial_with_stats(x).map_err(|err| err.to_string())?;
+    verify_polynomial(&receipt).map_err(|err| err.to_string())?;
+    match format {
+        Format::Dec => println!("{}", y),
+        Format::Hex => println!("{:#x}", y),
+        Format::Bytes => {
+            let hex: String = receipt
+                .journal
+                .bytes
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            println!("{}", hex);
+        }
+    }
+
+    if let Some(path) = json_meta {
+        let meta = ProveMeta {
+            x,
+            y,
+            image_id: image_id_hex(POLYNOMIAL_ID),
+            segments: stats.segments,
+            cycles: stats.cycles,
+        };
+        let json = serde_json::to_string_pretty(&meta).map_err(|err| err.to_string())?;
+        fs::write(path, json).map_err(|err| format!("failed to write {}: {}", path, err))?;
+    }
+
+    Ok(())
+}
 
 fn main() {
-    let (receipt, _) = polynomial(3);
-    receipt.verify(POLYNOMIAL_ID).expect("Verification failed");
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        None => {
+            let (receipt, _) = polynomial(3);
+            verify_polynomial(&receipt)
+                .map(|_| ())
+                .map_err(|err| err.to_string())
+        }
+        Some("prove") => prove(&args[1..]),
+        Some(_) => Err(USAGE.to_string()),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
 }
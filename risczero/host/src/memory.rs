@@ -0,0 +1,49 @@
+use risc0_zkvm::Receipt;
+
+use crate::polynomial;
+
+// Peak resident memory in bytes of this process and of the prover
+// subprocesses it has waited for, as reported by getrusage.
+#[cfg(unix)]
+fn peak_memory() -> usize {
+    fn max_rss(who: libc::c_int) -> usize {
+        // SAFETY: getrusage only writes into the provided struct.
+        let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+        if unsafe { libc::getrusage(who, &mut usage) } != 0 {
+            return 0;
+        }
+        // ru_maxrss is reported in bytes on macOS and in kilobytes elsewhere
+        if cfg!(target_os = "macos") {
+            usage.ru_maxrss as usize
+        } else {
+            usage.ru_maxrss as usize * 1024
+        }
+    }
+
+    max_rss(libc::RUSAGE_SELF).max(max_rss(libc::RUSAGE_CHILDREN))
+}
+
+#[cfg(not(unix))]
+fn peak_memory() -> usize {
+    0
+}
+
+// Compute the polynomial in zkVM and report the memory high-water mark in bytes.
+// The mark never decreases, so it covers everything proven by this process so far.
+pub fn polynomial_with_memory(x: u64) -> (Receipt, u64, usize) {
+    let (receipt, output) = polynomial(x);
+
+    (receipt, output, peak_memory())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polynomial_with_memory() {
+        let (_, output, peak) = polynomial_with_memory(3);
+        assert_eq!(output, 35);
+        assert!(peak > std::mem::size_of::<u64>());
+    }
+}
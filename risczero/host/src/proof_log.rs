@@ -0,0 +1,100 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use risc0_zkvm::sha::{Digest, Digestible};
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+
+use crate::{journal_digest, peek_output};
+
+/// A line of the proof log written by [`log_proof`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofLogEntry {
+    /// Seconds since the Unix epoch when the entry was appended.
+    pub timestamp: u64,
+    pub x: u64,
+    /// The output committed to the journal, if the guest committed one. It is
+    /// read without verifying the receipt.
+    pub output: Option<u64>,
+    pub journal_digest_hex: String,
+    /// The image id claimed by the receipt.
+    pub image_id_hex: String,
+}
+
+// Append an entry for the receipt to the log at `path`, creating the file if
+// needed. Each entry is one JSON line written with a single call, and existing
+// lines are never rewritten, so the log only grows.
+pub fn log_proof(path: &Path, x: u64, receipt: &Receipt) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_secs();
+    let image_id = receipt
+        .claim()
+        .expect("The receipt does not contain a claim")
+        .as_value()
+        .expect("The receipt claim is pruned")
+        .pre
+        .digest();
+
+    let entry = ProofLogEntry {
+        timestamp,
+        x,
+        output: peek_output(receipt).ok(),
+        journal_digest_hex: Digest::from(journal_digest(receipt)).to_string(),
+        image_id_hex: image_id.to_string(),
+    };
+    // all fields are strings and integers, so serialization cannot fail
+    let line = serde_json::to_string(&entry).expect("log entry is serializable") + "\n";
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .unwrap_or_else(|err| panic!("Failed to append to {}: {}", path.display(), err));
+}
+
+// Read back the entries of a log written by log_proof, oldest first
+pub fn read_proof_log(path: &Path) -> Vec<ProofLogEntry> {
+    let contents = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("Failed to read {}: {}", path.display(), err));
+
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            serde_json::from_str(line).unwrap_or_else(|err| {
+                panic!("{}:{}: malformed log entry: {}", path.display(), i + 1, err)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fake_receipt, image_id_hex};
+    use methods::POLYNOMIAL_ID;
+
+    #[test]
+    fn test_proof_log() {
+        let path =
+            std::env::temp_dir().join(format!("host-proof-log-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        log_proof(&path, 3, &fake_receipt(3, 35));
+        log_proof(&path, 4, &fake_receipt(4, 73));
+        let entries = read_proof_log(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].x, entries[0].output), (3, Some(35)));
+        assert_eq!((entries[1].x, entries[1].output), (4, Some(73)));
+        assert!(entries[0].timestamp <= entries[1].timestamp);
+        assert_ne!(entries[0].journal_digest_hex, entries[1].journal_digest_hex);
+        assert_eq!(entries[0].image_id_hex, image_id_hex(POLYNOMIAL_ID));
+    }
+}
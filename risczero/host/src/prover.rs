@@ -0,0 +1,155 @@
+use std::rc::Rc;
+
+use anyhow::anyhow;
+use risc0_zkvm::{
+    default_executor, default_prover, BonsaiProver, ExecutorEnv, ExternalProver, FakeReceipt,
+    InnerReceipt, ProveInfo, Prover, ProverOpts, Receipt, SessionStats, VerifierContext,
+};
+
+use crate::PolynomialError;
+
+/// The backend used to prove the guest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProverKind {
+    /// Prove on this machine with the `r0vm` found on the `PATH`.
+    #[default]
+    Local,
+    /// Prove remotely with Bonsai, configured by `BONSAI_API_URL` and `BONSAI_API_KEY`.
+    Bonsai,
+    /// Execute the guest without proving. The receipt is fake and only
+    /// verifies when `RISC0_DEV_MODE` is set.
+    Dev,
+    /// Prove in-process on the GPU when built with the `cuda` or `metal`
+    /// feature and a device is found at runtime, otherwise the same as
+    /// [`ProverKind::Local`].
+    Gpu,
+}
+
+impl ProverKind {
+    pub fn prover(self) -> Rc<dyn Prover> {
+        match self {
+            ProverKind::Local => Rc::new(ExternalProver::new("local", "r0vm")),
+            ProverKind::Bonsai => Rc::new(BonsaiProver::new("bonsai")),
+            ProverKind::Dev => Rc::new(DevProver),
+            #[cfg(any(feature = "cuda", feature = "metal"))]
+            ProverKind::Gpu if gpu_available() => Rc::new(risc0_zkvm::LocalProver::new("gpu")),
+            ProverKind::Gpu => ProverKind::Local.prover(),
+        }
+    }
+}
+
+// Whether the GPU prover finds a device. risc0 panics when it has none, so this
+// is checked before selecting it.
+#[cfg(feature = "cuda")]
+fn gpu_available() -> bool {
+    use cust::prelude::{CudaFlags, Device};
+
+    cust::init(CudaFlags::empty())
+        .and_then(|()| Device::get_device(0))
+        .is_ok()
+}
+
+#[cfg(all(feature = "metal", target_os = "macos", not(feature = "cuda")))]
+fn gpu_available() -> bool {
+    metal::Device::system_default().is_some()
+}
+
+#[cfg(all(feature = "metal", not(target_os = "macos"), not(feature = "cuda")))]
+fn gpu_available() -> bool {
+    false
+}
+
+/// Holds a prover handle so many inputs can be proven without setting it up again.
+pub struct PolynomialProverCtx {
+    prover: Rc<dyn Prover>,
+}
+
+impl PolynomialProverCtx {
+    /// Uses the prover selected by `default_prover`.
+    pub fn new() -> Self {
+        Self {
+            prover: default_prover(),
+        }
+    }
+
+    pub fn with_kind(kind: ProverKind) -> Self {
+        Self {
+            prover: kind.prover(),
+        }
+    }
+
+    // Compute the polynomial y = x^3 + x + 5 in zkVM with the cached prover
+    pub fn prove(&self, x: u64) -> Result<(Receipt, u64), PolynomialError> {
+        crate::prove(self.prover.as_ref(), x).map(|(receipt, output, _)| (receipt, output))
+    }
+}
+
+impl Default for PolynomialProverCtx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Executes the guest and wraps the claim in a fake receipt
+struct DevProver;
+
+impl Prover for DevProver {
+    fn get_name(&self) -> String {
+        "dev".to_string()
+    }
+
+    fn prove_with_ctx(
+        &self,
+        env: ExecutorEnv<'_>,
+        _ctx: &VerifierContext,
+        elf: &[u8],
+        _opts: &ProverOpts,
+    ) -> anyhow::Result<ProveInfo> {
+        let session = default_executor().execute(env, elf)?;
+        let claim = session
+            .receipt_claim
+            .clone()
+            .ok_or_else(|| anyhow!("the session has no receipt claim"))?;
+
+        let stats = SessionStats {
+            segments: session.segments.len(),
+            total_cycles: session.segments.iter().map(|s| 1u64 << s.po2).sum(),
+            user_cycles: session.cycles(),
+            paging_cycles: 0,
+            reserved_cycles: 0,
+        };
+        let receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(claim)),
+            session.journal.bytes,
+        );
+
+        Ok(ProveInfo { receipt, stats })
+    }
+
+    fn compress(&self, _opts: &ProverOpts, receipt: &Receipt) -> anyhow::Result<Receipt> {
+        Ok(receipt.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prover_ctx() {
+        let ctx = PolynomialProverCtx::new();
+        for (x, y) in [(1, 7), (2, 15), (3, 35)] {
+            let (_, output) = ctx.prove(x).unwrap();
+            assert_eq!(output, y);
+        }
+    }
+
+    // Proves on the CPU when the enabled feature finds no device
+    #[cfg(any(feature = "cuda", feature = "metal"))]
+    #[test]
+    fn test_gpu_prover() {
+        let (receipt, output) = crate::polynomial_with(ProverKind::Gpu, 3).unwrap();
+        assert_eq!(output, 35);
+        crate::verify_polynomial(&receipt).unwrap();
+    }
+}
@@ -0,0 +1,241 @@
+use std::fmt;
+
+use methods::POLYNOMIAL_ID;
+use polynomial_core::PolynomialResult;
+use risc0_zkvm::{default_prover, FakeReceipt, InnerReceipt, ProverOpts, Receipt, ReceiptClaim};
+
+use crate::polynomial;
+
+/// Errors reported when loading a serialized receipt.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The input is longer than the caller allows.
+    TooLarge { len: usize, max_len: usize },
+    /// The input is not a bincode encoded receipt.
+    Malformed(bincode::Error),
+    /// The journal does not contain a `PolynomialResult`.
+    Journal(risc0_zkvm::serde::Error),
+    /// The guest committed a status other than an output.
+    NoOutput(PolynomialResult),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { len, max_len } => {
+                write!(f, "receipt is {} bytes, the limit is {}", len, max_len)
+            }
+            Self::Malformed(err) => write!(f, "failed to decode the receipt: {}", err),
+            Self::Journal(err) => write!(f, "failed to decode the journal: {}", err),
+            Self::NoOutput(result) => write!(f, "the guest committed {:?}", result),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+// Deserialize a receipt received from an untrusted source. The length is checked
+// before decoding, so an oversized payload is rejected without allocating for it.
+pub fn load_receipt_limited(bytes: &[u8], max_len: usize) -> Result<Receipt, DecodeError> {
+    if bytes.len() > max_len {
+        return Err(DecodeError::TooLarge {
+            len: bytes.len(),
+            max_len,
+        });
+    }
+
+    bincode::deserialize(bytes).map_err(DecodeError::Malformed)
+}
+
+// Read the output committed to the journal WITHOUT verifying the seal. The
+// result is untrusted: anyone can build a receipt with any journal. Use it for
+// previews only, and `verify_polynomial` before acting on the output.
+pub fn peek_output(receipt: &Receipt) -> Result<u64, DecodeError> {
+    match receipt.journal.decode().map_err(DecodeError::Journal)? {
+        PolynomialResult::Ok(output) => Ok(output),
+        result => Err(DecodeError::NoOutput(result)),
+    }
+}
+
+// Build a receipt whose journal says the guest computed y, without running it.
+// The receipt is UNSOUND: it has no seal, and verifies only when
+// RISC0_DEV_MODE is set. Use it to test code that decodes or routes receipts.
+// The guest does not commit x, so it is ignored.
+pub fn fake_receipt(_x: u64, y: u64) -> Receipt {
+    let words = risc0_zkvm::serde::to_vec(&PolynomialResult::Ok(y)).unwrap();
+    let journal: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    let claim = ReceiptClaim::ok(POLYNOMIAL_ID, journal.clone());
+
+    Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal)
+}
+
+// Prove the polynomial and return the size of the receipt's seal in bytes. The
+// size depends on the receipt kind: composite and succinct STARK seals take
+// hundreds of kilobytes, a Groth16 seal a few hundred bytes.
+pub fn seal_size(x: u64) -> (Receipt, usize) {
+    let (receipt, _) = polynomial(x);
+    let size = receipt.seal_size();
+
+    (receipt, size)
+}
+
+/// How far a receipt is compressed after proving.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompressionLevel {
+    /// Keep the composite receipt, one STARK per segment.
+    #[default]
+    None,
+    /// Aggregate the segments into a single STARK.
+    Succinct,
+    /// Wrap the succinct STARK in a Groth16 SNARK. Needs Docker on x86_64.
+    Groth16,
+}
+
+// Prove the polynomial, compress the receipt to the given level, and return it
+// with its seal size in bytes. Each level trades proving time for a smaller seal.
+pub fn polynomial_compressed(x: u64, level: CompressionLevel) -> (Receipt, usize) {
+    let (receipt, _) = polynomial(x);
+    let receipt = match level {
+        CompressionLevel::None => receipt,
+        CompressionLevel::Succinct => default_prover()
+            .compress(&ProverOpts::succinct(), &receipt)
+            .unwrap(),
+        CompressionLevel::Groth16 => default_prover()
+            .compress(&ProverOpts::groth16(), &receipt)
+            .unwrap(),
+    };
+    let size = receipt.seal_size();
+
+    (receipt, size)
+}
+
+/// The proof system behind a receipt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReceiptKind {
+    /// One STARK per segment, as returned by the default prover.
+    Composite,
+    /// A single STARK aggregating all segments.
+    Succinct,
+    /// A Groth16 SNARK wrapping a succinct receipt.
+    Groth16,
+    /// A receipt without a seal, for development only.
+    Fake,
+    /// A kind added by a newer `risc0-zkvm`.
+    Unknown,
+}
+
+// Report the kind of the receipt's seal
+pub fn receipt_kind(receipt: &Receipt) -> ReceiptKind {
+    match &receipt.inner {
+        InnerReceipt::Composite(_) => ReceiptKind::Composite,
+        InnerReceipt::Succinct(_) => ReceiptKind::Succinct,
+        InnerReceipt::Groth16(_) => ReceiptKind::Groth16,
+        InnerReceipt::Fake(_) => ReceiptKind::Fake,
+        _ => ReceiptKind::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify_polynomial;
+
+    #[test]
+    fn test_load_receipt_limited() {
+        let (receipt, _) = polynomial(3);
+        let bytes = bincode::serialize(&receipt).unwrap();
+
+        let loaded = load_receipt_limited(&bytes, bytes.len()).unwrap();
+        assert_eq!(verify_polynomial(&loaded).unwrap(), 35);
+
+        assert!(matches!(
+            load_receipt_limited(&bytes, bytes.len() - 1),
+            Err(DecodeError::TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_peek_output() {
+        let (receipt, _) = polynomial(3);
+        assert_eq!(peek_output(&receipt).unwrap(), 35);
+
+        // the seal is not checked: a fake receipt with the same claim peeks the
+        // same output but does not verify
+        let claim = receipt.claim().unwrap().as_value().unwrap().clone();
+        let fake = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(claim)),
+            receipt.journal.bytes.clone(),
+        );
+        assert_eq!(peek_output(&fake).unwrap(), 35);
+        assert!(verify_polynomial(&fake).is_err());
+    }
+
+    #[test]
+    fn test_fake_receipt() {
+        let receipt = fake_receipt(3, 35);
+        assert_eq!(peek_output(&receipt).unwrap(), 35);
+        assert_eq!(receipt_kind(&receipt), ReceiptKind::Fake);
+    }
+
+    #[test]
+    fn test_seal_size() {
+        let (receipt, size) = seal_size(3);
+        assert!(size > 0);
+        // the default prover returns a composite receipt of STARK seals, one per
+        // segment, far larger than a Groth16 seal
+        assert!(size > 1024, "seal is only {} bytes", size);
+        verify_polynomial(&receipt).unwrap();
+    }
+
+    #[test]
+    fn test_receipt_kind() {
+        let (receipt, _) = polynomial(3);
+        assert_eq!(receipt_kind(&receipt), ReceiptKind::Composite);
+
+        let succinct = default_prover()
+            .compress(&ProverOpts::succinct(), &receipt)
+            .unwrap();
+        assert_eq!(receipt_kind(&succinct), ReceiptKind::Succinct);
+        verify_polynomial(&succinct).unwrap();
+    }
+
+    #[test]
+    fn test_polynomial_compressed() {
+        let (composite, composite_size) = polynomial_compressed(3, CompressionLevel::None);
+        let (succinct, succinct_size) = polynomial_compressed(3, CompressionLevel::Succinct);
+        assert_eq!(receipt_kind(&composite), ReceiptKind::Composite);
+        assert_eq!(receipt_kind(&succinct), ReceiptKind::Succinct);
+        verify_polynomial(&composite).unwrap();
+        verify_polynomial(&succinct).unwrap();
+
+        assert!(
+            succinct_size <= composite_size,
+            "succinct seal is {} bytes, composite {}",
+            succinct_size,
+            composite_size
+        );
+    }
+
+    #[cfg(feature = "groth16")]
+    #[test]
+    fn test_polynomial_compressed_groth16() {
+        let (_, succinct_size) = polynomial_compressed(3, CompressionLevel::Succinct);
+        let (groth16, groth16_size) = polynomial_compressed(3, CompressionLevel::Groth16);
+        assert_eq!(receipt_kind(&groth16), ReceiptKind::Groth16);
+        verify_polynomial(&groth16).unwrap();
+
+        assert!(groth16_size < succinct_size);
+    }
+
+    #[cfg(feature = "groth16")]
+    #[test]
+    fn test_receipt_kind_groth16() {
+        let (receipt, _) = polynomial(3);
+
+        let groth16 = default_prover()
+            .compress(&ProverOpts::groth16(), &receipt)
+            .unwrap();
+        assert_eq!(receipt_kind(&groth16), ReceiptKind::Groth16);
+        verify_polynomial(&groth16).unwrap();
+    }
+}
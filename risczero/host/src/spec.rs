@@ -0,0 +1,63 @@
+use methods::POLYNOMIAL_SPEC_ELF;
+use polynomial_core::{PolynomialResult, PolynomialSpec};
+use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
+
+use crate::{into_output, PolynomialError};
+
+// Parse a polynomial spec from TOML
+pub fn load_spec(toml: &str) -> Result<PolynomialSpec, toml::de::Error> {
+    toml::from_str(toml)
+}
+
+// Compute the polynomial described by `spec` in zkVM
+pub fn polynomial_from_spec(
+    spec: &PolynomialSpec,
+    x: u64,
+) -> Result<(Receipt, u64), PolynomialError> {
+    let env = ExecutorEnv::builder()
+        // send the spec and x to the guest
+        .write(spec)
+        .unwrap()
+        .write(&x)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let receipt = default_prover()
+        .prove(env, POLYNOMIAL_SPEC_ELF)
+        .map_err(PolynomialError::Prove)?
+        .receipt;
+    let (committed, result): (PolynomialSpec, PolynomialResult) =
+        receipt.journal.decode().map_err(PolynomialError::Decode)?;
+    assert_eq!(&committed, spec, "The guest committed a different spec");
+
+    into_output(result, receipt, x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use methods::POLYNOMIAL_SPEC_ID;
+
+    // shared with the Halo2 backend, which must expose the same y
+    const SPEC: &str = include_str!("../../core/example_spec.toml");
+
+    #[test]
+    fn test_polynomial_from_spec() {
+        let spec = load_spec(SPEC).unwrap();
+        assert_eq!(spec.coeffs, vec![5, 1, 0, 1]);
+
+        // 10^3 + 10 + 5 = 1015
+        let (receipt, output) = polynomial_from_spec(&spec, 10).unwrap();
+        assert_eq!(PolynomialResult::Ok(output), spec.evaluate(10));
+        assert_eq!(output, 15);
+        receipt
+            .verify(POLYNOMIAL_SPEC_ID)
+            .expect("Verification failed");
+
+        assert!(matches!(
+            polynomial_from_spec(&spec, 101),
+            Err(PolynomialError::TooLarge { x: 101, .. })
+        ));
+    }
+}
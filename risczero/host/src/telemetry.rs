@@ -0,0 +1,87 @@
+use std::time::Instant;
+
+use opentelemetry::trace::{TraceContextExt, Tracer};
+use opentelemetry::KeyValue;
+use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
+
+use crate::{prove_env, verify_polynomial};
+
+// Compute the polynomial in zkVM like `polynomial`, recording a "polynomial"
+// span with the children "env_build", "prove" and "verify". Each carries x, and
+// "prove" also the cycle count and its duration. The spans go wherever the
+// tracer's provider exports them, e.g. an OTLP collector.
+pub fn polynomial_with_spans<T>(tracer: &T, x: u64) -> (Receipt, u64)
+where
+    T: Tracer,
+    T::Span: Send + Sync + 'static,
+{
+    // OpenTelemetry has no unsigned attributes
+    let x_attr = KeyValue::new("x", x as i64);
+
+    tracer.in_span("polynomial", |cx| {
+        cx.span().set_attribute(x_attr.clone());
+
+        let env = tracer.in_span("env_build", |cx| {
+            cx.span().set_attribute(x_attr.clone());
+            ExecutorEnv::builder()
+                // send x to the guest
+                .write(&x)
+                .unwrap()
+                .build()
+                .unwrap()
+        });
+
+        let (receipt, output) = tracer.in_span("prove", |cx| {
+            let span = cx.span();
+            span.set_attribute(x_attr.clone());
+
+            let start = Instant::now();
+            let (receipt, output, stats) = prove_env(default_prover().as_ref(), env, x)
+                .unwrap_or_else(|err| panic!("{}", err));
+            span.set_attribute(KeyValue::new("cycles", stats.cycles as i64));
+            span.set_attribute(KeyValue::new(
+                "duration_ms",
+                start.elapsed().as_millis() as i64,
+            ));
+
+            (receipt, output)
+        });
+
+        tracer.in_span("verify", |cx| {
+            cx.span().set_attribute(x_attr.clone());
+            verify_polynomial(&receipt).expect("Verification failed");
+        });
+
+        (receipt, output)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry::Value;
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+    use opentelemetry_sdk::trace::TracerProvider;
+
+    #[test]
+    fn test_polynomial_with_spans() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+
+        let (_, output) = polynomial_with_spans(&provider.tracer("host"), 3);
+        assert_eq!(output, 35);
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let prove = spans
+            .iter()
+            .find(|span| span.name == "prove")
+            .expect("no prove span was recorded");
+        assert!(prove
+            .attributes
+            .iter()
+            .any(|kv| kv.key.as_str() == "x" && kv.value == Value::I64(3)));
+    }
+}
@@ -0,0 +1,388 @@
+use methods::{
+    POLYNOMIAL_BIVARIATE_ELF, POLYNOMIAL_BYTES_ELF, POLYNOMIAL_COMMIT_ELF, POLYNOMIAL_COMPARE_ELF,
+    POLYNOMIAL_CUBIC_ELF, POLYNOMIAL_DIGIT_SUM_ELF, POLYNOMIAL_FORWARD_DIFF_ELF,
+    POLYNOMIAL_NONCE_ELF, POLYNOMIAL_PROGRAM_ELF, POLYNOMIAL_QUADRATIC_ELF,
+    POLYNOMIAL_QUADRATIC_ROOT_ELF, POLYNOMIAL_SATURATING_ELF, POLYNOMIAL_SEEDED_ELF,
+    POLYNOMIAL_SUM_ELF, POLYNOMIAL_THRESHOLD_ELF, POLYNOMIAL_VEC_BOUNDED_ELF,
+};
+use polynomial_core::{evaluate, evaluate_sum, Op, PolynomialResult, ProgramError};
+use risc0_zkvm::sha::{Impl, Sha256};
+use risc0_zkvm::Receipt;
+
+use crate::{prove_guest, PolynomialError};
+
+// Map an output the guest committed as None on overflow to the output, or to
+// an overflow of x
+fn checked<T>(
+    (receipt, output): (Receipt, Option<T>),
+    x: u64,
+) -> Result<(Receipt, T), PolynomialError> {
+    match output {
+        Some(output) => Ok((receipt, output)),
+        None => Err(PolynomialError::Overflow {
+            x,
+            receipt: Box::new(receipt),
+        }),
+    }
+}
+
+// Compute the bivariate polynomial z = x^3 + y + 5 in zkVM
+pub fn polynomial_bivariate(x: u64, y: u64) -> Result<(Receipt, u64), PolynomialError> {
+    checked(prove_guest(POLYNOMIAL_BIVARIATE_ELF, &(x, y)), x)
+}
+
+// Prove that y = x^3 + x + 5 is below a public threshold without revealing y
+pub fn polynomial_threshold(x: u64, threshold: u64) -> Result<(Receipt, bool), PolynomialError> {
+    let (receipt, (below, committed)): (_, (Option<bool>, u64)) =
+        prove_guest(POLYNOMIAL_THRESHOLD_ELF, &(x, threshold));
+    assert_eq!(
+        committed, threshold,
+        "The guest committed a different threshold"
+    );
+
+    checked((receipt, below), x)
+}
+
+// Prove whether f(x1) < f(x2) without revealing x1, x2 or their values
+pub fn polynomial_compare(x1: u64, x2: u64) -> Result<(Receipt, bool), PolynomialError> {
+    // the guest commits no y, the overflow is reported for the input causing it
+    let x = if evaluate(x1).is_none() { x1 } else { x2 };
+    checked(prove_guest(POLYNOMIAL_COMPARE_ELF, &(x1, x2)), x)
+}
+
+// Compute the polynomial in zkVM for x encoded as 8 little-endian bytes
+pub fn polynomial_bytes(input: &[u8]) -> (Receipt, PolynomialResult) {
+    prove_guest(POLYNOMIAL_BYTES_ELF, &input)
+}
+
+// Prove the sum of the polynomial over several inputs without revealing each y
+pub fn polynomial_sum(xs: &[u64]) -> Result<(Receipt, u64), PolynomialError> {
+    // the overflow is reported for the first x whose term does not fit the sum
+    let x = (1..=xs.len())
+        .find(|&n| evaluate_sum(&xs[..n]).is_none())
+        .map_or(0, |n| xs[n - 1]);
+    checked(prove_guest(POLYNOMIAL_SUM_ELF, &xs), x)
+}
+
+// Compute the polynomial in zkVM for each x, checked against its own bound
+pub fn polynomial_vec_bounded(xs: &[u64], bounds: &[u64]) -> (Receipt, Vec<PolynomialResult>) {
+    assert_eq!(xs.len(), bounds.len(), "each x needs a bound");
+    prove_guest(POLYNOMIAL_VEC_BOUNDED_ELF, &(xs, bounds))
+}
+
+// Evaluate a polynomial written in the stack bytecode for x in zkVM
+pub fn polynomial_program(ops: Vec<Op>, x: u64) -> (Receipt, Result<u64, ProgramError>) {
+    prove_guest(POLYNOMIAL_PROGRAM_ELF, &(ops, x))
+}
+
+// Compute y = x^3 + x + 5 and the sum of its base-10 digits in zkVM
+pub fn polynomial_digit_sum(x: u64) -> Result<(Receipt, u64, u64), PolynomialError> {
+    let (receipt, (output, digit_sum)) = checked(prove_guest(POLYNOMIAL_DIGIT_SUM_ELF, &x), x)?;
+
+    Ok((receipt, output, digit_sum))
+}
+
+// Compute the polynomial in zkVM, binding the proof to a caller chosen nonce
+pub fn polynomial_nonce(x: u64, nonce: u64) -> Result<(Receipt, u64), PolynomialError> {
+    let (receipt, (output, committed)): (_, (Option<u64>, u64)) =
+        prove_guest(POLYNOMIAL_NONCE_ELF, &(x, nonce));
+    assert_eq!(committed, nonce, "The guest committed a different nonce");
+
+    checked((receipt, output), x)
+}
+
+// Compute the polynomial in zkVM for an x derived from the seed, below the bound.
+// Returns x and y, so a proof can be reproduced from the seed alone.
+pub fn polynomial_seeded(seed: u64, bound: u64) -> Result<(Receipt, u64, u64), PolynomialError> {
+    let (receipt, (committed, x, y)): (_, (u64, u64, Option<u64>)) =
+        prove_guest(POLYNOMIAL_SEEDED_ELF, &(seed, bound));
+    assert_eq!(committed, seed, "The guest committed a different seed");
+
+    let (receipt, y) = checked((receipt, y), x)?;
+    Ok((receipt, x, y))
+}
+
+// Compute the polynomial in zkVM, committing only to sha256(y || r) for a
+// blinding factor r. The host reveals y and r later, see open_commit.
+pub fn polynomial_commit(x: u64, r: [u8; 32]) -> Result<(Receipt, [u8; 32]), PolynomialError> {
+    checked(prove_guest(POLYNOMIAL_COMMIT_ELF, &(x, r)), x)
+}
+
+// Recompute the commitment of polynomial_commit from the revealed y and r
+pub fn open_commit(y: u64, r: [u8; 32]) -> [u8; 32] {
+    let mut preimage = [0u8; 40];
+    preimage[..8].copy_from_slice(&y.to_le_bytes());
+    preimage[8..].copy_from_slice(&r);
+
+    Impl::hash_bytes(&preimage).as_bytes().try_into().unwrap()
+}
+
+// Compute f(x) and f(x + 1) in zkVM, returning both and their difference
+pub fn polynomial_forward_diff(x: u64) -> Result<(Receipt, u64, u64, u64), PolynomialError> {
+    let (receipt, (y, y_next, diff)) = checked(prove_guest(POLYNOMIAL_FORWARD_DIFF_ELF, &x), x)?;
+
+    Ok((receipt, y, y_next, diff))
+}
+
+// Compute the polynomial in zkVM, clamping to u64::MAX instead of overflowing.
+// The flag tells whether y saturated.
+pub fn polynomial_saturating(x: u64) -> (Receipt, u64, bool) {
+    let (receipt, (output, saturated)) = prove_guest(POLYNOMIAL_SATURATING_ELF, &x);
+
+    (receipt, output, saturated)
+}
+
+// Prove whether a private x solves a * x^2 + b * x + c = 0
+pub fn polynomial_quadratic_root(
+    a: i64,
+    b: i64,
+    c: i64,
+    x: i64,
+) -> Result<(Receipt, bool), PolynomialError> {
+    let (receipt, (ca, cb, cc, is_root)): (_, (i64, i64, i64, Option<bool>)) =
+        prove_guest(POLYNOMIAL_QUADRATIC_ROOT_ELF, &((a, b, c), x));
+    assert_eq!(
+        (ca, cb, cc),
+        (a, b, c),
+        "The guest committed different coefficients"
+    );
+
+    // the error holds |x|, the magnitude that overflows
+    checked((receipt, is_root), x.unsigned_abs())
+}
+
+// Compute y = x^3 + x + 5 with the guest generated by define_polynomial_guest!
+pub fn polynomial_cubic(x: u64) -> (Receipt, PolynomialResult) {
+    prove_guest(POLYNOMIAL_CUBIC_ELF, &x)
+}
+
+// Compute y = 2x^2 + 3 with the guest generated by define_polynomial_guest!
+pub fn polynomial_quadratic(x: u64) -> (Receipt, PolynomialResult) {
+    prove_guest(POLYNOMIAL_QUADRATIC_ELF, &x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use methods::{
+        POLYNOMIAL_BIVARIATE_ID, POLYNOMIAL_BYTES_ID, POLYNOMIAL_COMMIT_ID, POLYNOMIAL_COMPARE_ID,
+        POLYNOMIAL_CUBIC_ID, POLYNOMIAL_DIGIT_SUM_ID, POLYNOMIAL_FORWARD_DIFF_ID,
+        POLYNOMIAL_NONCE_ID, POLYNOMIAL_PROGRAM_ID, POLYNOMIAL_QUADRATIC_ID,
+        POLYNOMIAL_QUADRATIC_ROOT_ID, POLYNOMIAL_SATURATING_ID, POLYNOMIAL_SEEDED_ID,
+        POLYNOMIAL_SUM_ID, POLYNOMIAL_THRESHOLD_ID, POLYNOMIAL_VEC_BOUNDED_ID,
+    };
+
+    #[test]
+    fn test_polynomial_bivariate() {
+        let (receipt, output) = polynomial_bivariate(3, 4).unwrap();
+        assert_eq!(output, 36, "The polynomial is z = x^3 + y + 5");
+        receipt
+            .verify(POLYNOMIAL_BIVARIATE_ID)
+            .expect("Verification failed");
+    }
+
+    #[test]
+    fn test_polynomial_threshold() {
+        // f(3) = 35
+        let (receipt, below) = polynomial_threshold(3, 40).unwrap();
+        assert!(below);
+        receipt
+            .verify(POLYNOMIAL_THRESHOLD_ID)
+            .expect("Verification failed");
+
+        let (_, below) = polynomial_threshold(3, 30).unwrap();
+        assert!(!below);
+    }
+
+    #[test]
+    fn test_polynomial_compare() {
+        // f(2) = 15 and f(3) = 35
+        let (receipt, less) = polynomial_compare(2, 3).unwrap();
+        assert!(less);
+        receipt
+            .verify(POLYNOMIAL_COMPARE_ID)
+            .expect("Verification failed");
+
+        let (_, less) = polynomial_compare(3, 2).unwrap();
+        assert!(!less);
+    }
+
+    #[test]
+    fn test_polynomial_bytes() {
+        let (receipt, result) = polynomial_bytes(&3u64.to_le_bytes());
+        assert_eq!(result, PolynomialResult::Ok(35));
+        receipt
+            .verify(POLYNOMIAL_BYTES_ID)
+            .expect("Verification failed");
+
+        let (_, result) = polynomial_bytes(&[3, 0, 0]);
+        assert_eq!(result, PolynomialResult::InvalidInput);
+    }
+
+    #[test]
+    fn test_polynomial_sum() {
+        let (receipt, sum) = polynomial_sum(&[1, 2, 3]).unwrap();
+        assert_eq!(sum, 7 + 15 + 35);
+        receipt
+            .verify(POLYNOMIAL_SUM_ID)
+            .expect("Verification failed");
+
+        // f(2^22) alone overflows, the guest still commits a receipt for it
+        match polynomial_sum(&[1, 1 << 22, 3]) {
+            Err(PolynomialError::Overflow { x, receipt }) => {
+                assert_eq!(x, 1 << 22);
+                receipt
+                    .verify(POLYNOMIAL_SUM_ID)
+                    .expect("Verification failed");
+            }
+            other => panic!("expected an overflow, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_polynomial_vec_bounded() {
+        let (receipt, results) = polynomial_vec_bounded(&[1, 5, 3], &[10, 4, 3]);
+        assert_eq!(
+            results,
+            vec![
+                PolynomialResult::Ok(7),
+                PolynomialResult::TooLarge,
+                PolynomialResult::Ok(35),
+            ]
+        );
+        receipt
+            .verify(POLYNOMIAL_VEC_BOUNDED_ID)
+            .expect("Verification failed");
+    }
+
+    #[test]
+    fn test_polynomial_program() {
+        // x^3 + x + 5
+        let ops = vec![
+            Op::PushX,
+            Op::PushX,
+            Op::Mul,
+            Op::PushX,
+            Op::Mul,
+            Op::PushX,
+            Op::Add,
+            Op::PushConst(5),
+            Op::Add,
+        ];
+        let (receipt, result) = polynomial_program(ops, 3);
+        assert_eq!(result, Ok(35));
+        receipt
+            .verify(POLYNOMIAL_PROGRAM_ID)
+            .expect("Verification failed");
+
+        let (_, result) = polynomial_program(vec![Op::PushX, Op::Add], 3);
+        assert_eq!(result, Err(ProgramError::StackUnderflow));
+    }
+
+    #[test]
+    fn test_polynomial_digit_sum() {
+        let (receipt, output, digit_sum) = polynomial_digit_sum(3).unwrap();
+        assert_eq!(output, 35);
+        assert_eq!(digit_sum, 8);
+        receipt
+            .verify(POLYNOMIAL_DIGIT_SUM_ID)
+            .expect("Verification failed");
+    }
+
+    #[test]
+    fn test_polynomial_nonce() {
+        let (receipt, output) = polynomial_nonce(3, 7).unwrap();
+        assert_eq!(output, 35);
+        receipt
+            .verify(POLYNOMIAL_NONCE_ID)
+            .expect("Verification failed");
+
+        let (_, nonce): (Option<u64>, u64) = receipt.journal.decode().unwrap();
+        assert_eq!(nonce, 7);
+
+        let (other, _) = polynomial_nonce(3, 8).unwrap();
+        assert_ne!(receipt.journal.bytes, other.journal.bytes);
+    }
+
+    #[test]
+    fn test_polynomial_seeded() {
+        let (receipt, x, y) = polynomial_seeded(42, 1000).unwrap();
+        assert!(x < 1000);
+        assert_eq!(Some(y), polynomial_core::evaluate(x));
+        receipt
+            .verify(POLYNOMIAL_SEEDED_ID)
+            .expect("Verification failed");
+
+        let (other, other_x, other_y) = polynomial_seeded(42, 1000).unwrap();
+        assert_eq!((other_x, other_y), (x, y));
+        assert_eq!(other.journal.bytes, receipt.journal.bytes);
+    }
+
+    #[test]
+    fn test_polynomial_commit() {
+        let r = [7u8; 32];
+        let (receipt, commitment) = polynomial_commit(3, r).unwrap();
+        assert_eq!(commitment, open_commit(35, r));
+        receipt
+            .verify(POLYNOMIAL_COMMIT_ID)
+            .expect("Verification failed");
+
+        // a wrong y or another blinding factor does not open the commitment
+        assert_ne!(commitment, open_commit(36, r));
+        assert_ne!(commitment, open_commit(35, [8u8; 32]));
+    }
+
+    #[test]
+    fn test_polynomial_forward_diff() {
+        let (receipt, y, y_next, diff) = polynomial_forward_diff(3).unwrap();
+        assert_eq!((y, y_next, diff), (35, 73, 38));
+        receipt
+            .verify(POLYNOMIAL_FORWARD_DIFF_ID)
+            .expect("Verification failed");
+    }
+
+    #[test]
+    fn test_polynomial_cubic() {
+        let (receipt, result) = polynomial_cubic(3);
+        assert_eq!(result, PolynomialResult::Ok(35));
+        receipt
+            .verify(POLYNOMIAL_CUBIC_ID)
+            .expect("Verification failed");
+    }
+
+    #[test]
+    fn test_polynomial_quadratic() {
+        let (receipt, result) = polynomial_quadratic(3);
+        assert_eq!(result, PolynomialResult::Ok(21));
+        receipt
+            .verify(POLYNOMIAL_QUADRATIC_ID)
+            .expect("Verification failed");
+    }
+
+    #[test]
+    fn test_polynomial_saturating() {
+        let (receipt, output, saturated) = polynomial_saturating(3_000_000);
+        assert_eq!(output, u64::MAX);
+        assert!(saturated);
+        receipt
+            .verify(POLYNOMIAL_SATURATING_ID)
+            .expect("Verification failed");
+
+        let (_, output, saturated) = polynomial_saturating(3);
+        assert_eq!(output, 35);
+        assert!(!saturated);
+    }
+
+    #[test]
+    fn test_polynomial_quadratic_root() {
+        // x^2 - 5x + 6 = (x - 2)(x - 3)
+        let (receipt, is_root) = polynomial_quadratic_root(1, -5, 6, 2).unwrap();
+        assert!(is_root);
+        receipt
+            .verify(POLYNOMIAL_QUADRATIC_ROOT_ID)
+            .expect("Verification failed");
+
+        let (_, is_root) = polynomial_quadratic_root(1, -5, 6, 4).unwrap();
+        assert!(!is_root);
+    }
+}
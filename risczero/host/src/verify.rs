@@ -0,0 +1,246 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use methods::POLYNOMIAL_ID;
+use polynomial_core::PolynomialResult;
+use risc0_zkp::verify::VerificationError;
+use risc0_zkvm::sha::{Digest, Digestible};
+use risc0_zkvm::{ExitCode, Receipt};
+
+/// Errors reported when verifying a polynomial receipt.
+#[derive(Debug)]
+pub enum VerifyError {
+    /// The receipt does not verify against the image id.
+    Verification(VerificationError),
+    /// The journal does not contain a `PolynomialResult`.
+    Decode(risc0_zkvm::serde::Error),
+    /// The receipt is valid, but the guest did not compute y.
+    Failed(PolynomialResult),
+    /// The signature over a [`crate::SignedProof`] does not match the key.
+    Signature(ed25519_dalek::SignatureError),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Verification(err) => write!(f, "verification failed: {}", err),
+            Self::Decode(err) => write!(f, "failed to decode the journal: {}", err),
+            Self::Failed(result) => write!(f, "the guest committed {:?}", result),
+            Self::Signature(err) => write!(f, "invalid signature: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// What a verified receipt of the polynomial guest claims.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClaimInfo {
+    pub output: u64,
+    pub exit_code: ExitCode,
+    /// Digest of the system state the guest started from
+    pub pre_state: [u8; 32],
+    /// Digest of the system state the guest ended in
+    pub post_state: [u8; 32],
+}
+
+// Verify a receipt of the polynomial guest built into this crate
+pub fn verify_polynomial(receipt: &Receipt) -> Result<u64, VerifyError> {
+    verify_against(receipt, POLYNOMIAL_ID)
+}
+
+// Verify a receipt of the polynomial guest, also returning how long it took
+pub fn verify_timed(receipt: &Receipt) -> (Result<u64, VerifyError>, Duration) {
+    let start = Instant::now();
+    let result = verify_polynomial(receipt);
+
+    (result, start.elapsed())
+}
+
+// Verify a receipt against the image id of a specific build of the guest
+pub fn verify_against(receipt: &Receipt, image_id: [u32; 8]) -> Result<u64, VerifyError> {
+    receipt
+        .verify(image_id)
+        .map_err(VerifyError::Verification)?;
+
+    match receipt.journal.decode().map_err(VerifyError::Decode)? {
+        PolynomialResult::Ok(output) => Ok(output),
+        result => Err(VerifyError::Failed(result)),
+    }
+}
+
+// Verify a receipt of the polynomial guest and return its full claim
+pub fn verify_detailed(receipt: &Receipt) -> Result<ClaimInfo, VerifyError> {
+    let output = verify_polynomial(receipt)?;
+    let claim = receipt.claim().map_err(VerifyError::Verification)?;
+    // a verified receipt always carries its claim, possibly pruned to digests
+    let claim = claim
+        .as_value()
+        .map_err(|_| VerifyError::Verification(VerificationError::ReceiptFormatError))?;
+
+    Ok(ClaimInfo {
+        output,
+        exit_code: claim.exit_code,
+        pre_state: claim.pre.digest().into(),
+        post_state: claim.post.digest().into(),
+    })
+}
+
+// Verify each receipt against POLYNOMIAL_ID. The results are in the order of the
+// receipts, and a receipt that fails does not affect the others.
+pub fn verify_batch(receipts: &[Receipt]) -> Vec<Result<u64, VerifyError>> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        receipts.par_iter().map(verify_polynomial).collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    receipts.iter().map(verify_polynomial).collect()
+}
+
+// Explain why a receipt does not verify against `expected`, if the cause is that
+// it was produced by another build of the guest. Returns None when the receipt
+// verifies, or when it fails for another reason, such as a tampered journal.
+pub fn explain_id_mismatch(receipt: &Receipt, expected: [u32; 8]) -> Option<String> {
+    if receipt.verify(expected).is_ok() {
+        return None;
+    }
+    let claim = receipt.claim().ok()?;
+    let actual = claim.as_value().ok()?.pre.digest();
+
+    // the claimed image id is only trusted if the receipt verifies against it
+    receipt.verify(actual).ok()?;
+    Some(format!(
+        "the receipt was produced by image {}, but {} was expected; \
+         the guest was likely rebuilt, so one side uses a stale binary",
+        actual,
+        Digest::from(expected)
+    ))
+}
+
+// Check whether two receipts attest the same statement. Seals differ between
+// proofs, so only the journals and the claim digests are compared.
+pub fn same_statement(a: &Receipt, b: &Receipt) -> bool {
+    match (a.claim(), b.claim()) {
+        (Ok(claim_a), Ok(claim_b)) => {
+            a.journal.bytes == b.journal.bytes && claim_a.digest() == claim_b.digest()
+        }
+        _ => false,
+    }
+}
+
+// Return the digest of the receipt claim, which is what an on-chain registry commits to
+pub fn claim_digest(receipt: &Receipt) -> [u8; 32] {
+    receipt
+        .claim()
+        .expect("The receipt does not contain a claim")
+        .digest()
+        .into()
+}
+
+// Return sha256 of the journal, a key for indexing receipts by their output.
+// Unlike the claim digest, it does not depend on the image id.
+pub fn journal_digest(receipt: &Receipt) -> [u8; 32] {
+    receipt.journal.digest().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial;
+
+    #[test]
+    fn test_verify_against() {
+        let (receipt, _) = polynomial(3);
+        assert_eq!(verify_against(&receipt, POLYNOMIAL_ID).unwrap(), 35);
+
+        let mut wrong_id = POLYNOMIAL_ID;
+        wrong_id[0] ^= 1;
+        assert!(matches!(
+            verify_against(&receipt, wrong_id),
+            Err(VerifyError::Verification(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_timed() {
+        let (receipt, _) = polynomial(3);
+        let (result, elapsed) = verify_timed(&receipt);
+        assert_eq!(result.unwrap(), 35);
+        assert!(elapsed > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_explain_id_mismatch() {
+        let (receipt, _) = polynomial(3);
+        assert_eq!(explain_id_mismatch(&receipt, POLYNOMIAL_ID), None);
+
+        let mut wrong_id = POLYNOMIAL_ID;
+        wrong_id[0] ^= 1;
+        let explanation = explain_id_mismatch(&receipt, wrong_id).unwrap();
+        assert!(explanation.contains(&Digest::from(POLYNOMIAL_ID).to_string()));
+        assert!(explanation.contains(&Digest::from(wrong_id).to_string()));
+
+        // a tampered journal is not an image id mismatch
+        let mut tampered = receipt.clone();
+        tampered.journal.bytes[4] ^= 1;
+        assert_eq!(explain_id_mismatch(&tampered, wrong_id), None);
+    }
+
+    #[test]
+    fn test_same_statement() {
+        let (a, _) = polynomial(3);
+        let (b, _) = polynomial(3);
+        let (c, _) = polynomial(4);
+
+        assert!(same_statement(&a, &b));
+        assert!(!same_statement(&a, &c));
+    }
+
+    #[test]
+    fn test_claim_digest() {
+        let (a, _) = polynomial(3);
+        let (b, _) = polynomial(3);
+        let (c, _) = polynomial(4);
+
+        assert_eq!(claim_digest(&a), claim_digest(&b));
+        assert_ne!(claim_digest(&a), claim_digest(&c));
+    }
+
+    #[test]
+    fn test_journal_digest() {
+        let (a, _) = polynomial(3);
+        let (b, _) = polynomial(3);
+        let (c, _) = polynomial(4);
+
+        assert_eq!(journal_digest(&a), journal_digest(&b));
+        assert_ne!(journal_digest(&a), journal_digest(&c));
+    }
+
+    #[test]
+    fn test_verify_detailed() {
+        let (receipt, _) = polynomial(3);
+        let info = verify_detailed(&receipt).unwrap();
+
+        assert_eq!(info.output, 35);
+        assert_eq!(info.exit_code, ExitCode::Halted(0));
+        assert_ne!(info.pre_state, info.post_state);
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let mut receipts: Vec<Receipt> = [1, 2, 3].map(|x| polynomial(x).0).to_vec();
+        let mut tampered = receipts[1].clone();
+        // y = 15 becomes 14
+        tampered.journal.bytes[4] ^= 1;
+        receipts.insert(2, tampered);
+
+        let results = verify_batch(&receipts);
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap(), &7);
+        assert_eq!(results[1].as_ref().unwrap(), &15);
+        assert!(matches!(results[2], Err(VerifyError::Verification(_))));
+        assert_eq!(results[3].as_ref().unwrap(), &35);
+    }
+}
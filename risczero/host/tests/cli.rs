@@ -0,0 +1,60 @@
+use std::fs;
+use std::process::Command;
+
+use host::image_id_hex;
+use methods::POLYNOMIAL_ID;
+
+#[test]
+fn test_prove_json_meta() {
+    let path = std::env::temp_dir().join(format!("host-meta-{}.json", std::process::id()));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_host"))
+        .args(["prove", "3", "--json-meta"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let meta: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(meta["x"], 3);
+    assert_eq!(meta["y"], 35);
+    assert_eq!(meta["image_id"], image_id_hex(POLYNOMIAL_ID));
+    assert!(meta["segments"].as_u64().unwrap() > 0);
+    assert!(meta["cycles"].as_u64().unwrap() > 0);
+}
+
+fn prove_stdout(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_host"))
+        .arg("prove")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // the guest's log lines are forwarded to stdout ahead of y
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    stdout.lines().last().unwrap_or_default().to_string()
+}
+
+#[test]
+fn test_prove_format() {
+    assert_eq!(prove_stdout(&["3"]), "35");
+    assert_eq!(prove_stdout(&["3", "--format", "dec"]), "35");
+    assert_eq!(prove_stdout(&["3", "--format", "hex"]), "0x23");
+    // PolynomialResult::Ok(35): the variant index, then y as two u32 words
+    assert_eq!(
+        prove_stdout(&["3", "--format", "bytes"]),
+        "000000002300000000000000"
+    );
+}
@@ -0,0 +1,19 @@
+use std::time::{Duration, Instant};
+
+use host::{polynomial_with, ProverKind};
+use methods::POLYNOMIAL_ID;
+
+// Fake receipts only verify with RISC0_DEV_MODE set, which risc0-zkvm reads
+// from the process environment. This test has a binary of its own, so setting
+// the variable cannot leak into the tests that expect real receipts.
+#[test]
+fn test_polynomial_with_dev() {
+    std::env::set_var("RISC0_DEV_MODE", "1");
+
+    let start = Instant::now();
+    let (receipt, output) = polynomial_with(ProverKind::Dev, 3).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(30));
+
+    assert_eq!(output, 35);
+    receipt.verify(POLYNOMIAL_ID).expect("Verification failed");
+}
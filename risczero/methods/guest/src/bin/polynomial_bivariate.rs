@@ -0,0 +1,15 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::evaluate_bivariate;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let x: u64 = env::read();
+    let y: u64 = env::read();
+    // None if z does not fit in a u64
+    let z = evaluate_bivariate(x, y);
+
+    env::commit(&z);
+}
@@ -0,0 +1,14 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use polynomial_core::PolynomialResult;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let input: Vec<u8> = env::read();
+    env::commit(&PolynomialResult::from_bytes(&input));
+}
@@ -0,0 +1,22 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::evaluate;
+use risc0_zkvm::guest::env;
+use risc0_zkvm::sha::{Impl, Sha256};
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let x: u64 = env::read();
+    let r: [u8; 32] = env::read();
+
+    // only sha256(y || r) is public, y stays hidden until the host reveals it with r
+    let h: Option<[u8; 32]> = evaluate(x).map(|y| {
+        let mut preimage = [0u8; 40];
+        preimage[..8].copy_from_slice(&y.to_le_bytes());
+        preimage[8..].copy_from_slice(&r);
+        Impl::hash_bytes(&preimage).as_bytes().try_into().unwrap()
+    });
+
+    env::commit(&h);
+}
@@ -0,0 +1,16 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::evaluate;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let x1: u64 = env::read();
+    let x2: u64 = env::read();
+
+    // only the outcome of the comparison is public, None if either y overflows
+    let less = evaluate(x1).zip(evaluate(x2)).map(|(y1, y2)| y1 < y2);
+
+    env::commit(&less);
+}
@@ -0,0 +1,5 @@
+#![no_main]
+#![no_std]
+
+// y = x^3 + x + 5
+polynomial_core::define_polynomial_guest!(cubic, [5, 1, 0, 1]);
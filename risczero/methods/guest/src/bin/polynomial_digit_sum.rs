@@ -0,0 +1,23 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::evaluate;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let x: u64 = env::read();
+
+    // sum the base-10 digits of y, None if y overflows
+    let result = evaluate(x).map(|y| {
+        let mut rest = y;
+        let mut digit_sum = 0;
+        while rest > 0 {
+            digit_sum += rest % 10;
+            rest /= 10;
+        }
+        (y, digit_sum)
+    });
+
+    env::commit(&result);
+}
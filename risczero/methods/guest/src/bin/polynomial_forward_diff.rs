@@ -0,0 +1,18 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::evaluate;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let x: u64 = env::read();
+    let y = evaluate(x);
+    let y_next = x.checked_add(1).and_then(evaluate);
+
+    // f is increasing, so the forward difference is never negative. None if
+    // f(x + 1) overflows, which covers f(x) too.
+    let result = y.zip(y_next).map(|(y, y_next)| (y, y_next, y_next - y));
+
+    env::commit(&result);
+}
@@ -0,0 +1,15 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::evaluate;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let x: u64 = env::read();
+    let nonce: u64 = env::read();
+    let y = evaluate(x);
+
+    // binding the nonce to y makes every proof unique, so it cannot be replayed
+    env::commit(&(y, nonce));
+}
@@ -0,0 +1,16 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use polynomial_core::{run_program, Op};
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let ops: Vec<Op> = env::read();
+    let x: u64 = env::read();
+
+    env::commit(&run_program(&ops, x));
+}
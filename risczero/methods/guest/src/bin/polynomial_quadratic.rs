@@ -0,0 +1,5 @@
+#![no_main]
+#![no_std]
+
+// y = 2x^2 + 3
+polynomial_core::define_polynomial_guest!(quadratic, [3, 0, 2]);
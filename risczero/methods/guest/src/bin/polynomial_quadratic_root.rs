@@ -0,0 +1,15 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::is_quadratic_root;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let (a, b, c): (i64, i64, i64) = env::read();
+    let x: i64 = env::read();
+    let is_root = is_quadratic_root(a, b, c, x);
+
+    // x stays private, the verifier learns which quadratic it solves
+    env::commit(&(a, b, c, is_root));
+}
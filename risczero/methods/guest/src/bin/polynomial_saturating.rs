@@ -0,0 +1,13 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::evaluate_saturating;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let x: u64 = env::read();
+    let (y, saturated) = evaluate_saturating(x);
+
+    env::commit(&(y, saturated));
+}
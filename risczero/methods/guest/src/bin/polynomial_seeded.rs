@@ -0,0 +1,20 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::evaluate;
+use risc0_zkvm::guest::env;
+use risc0_zkvm::sha::{Impl, Sha256};
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let seed: u64 = env::read();
+    let bound: u64 = env::read();
+    assert!(bound > 0, "bound must be positive");
+
+    // x is the first 8 bytes of sha256(seed), reduced below the bound
+    let digest = Impl::hash_bytes(&seed.to_le_bytes());
+    let x = u64::from_le_bytes(digest.as_bytes()[..8].try_into().unwrap()) % bound;
+    let y = evaluate(x);
+
+    env::commit(&(seed, x, y));
+}
@@ -0,0 +1,15 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::PolynomialSpec;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let spec: PolynomialSpec = env::read();
+    let x: u64 = env::read();
+    let result = spec.evaluate(x);
+
+    // the spec is public, so the verifier knows which polynomial was evaluated
+    env::commit(&(spec, result));
+}
@@ -0,0 +1,17 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use polynomial_core::evaluate_sum;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let xs: Vec<u64> = env::read();
+    // None if a term or the sum does not fit in a u64
+    let sum = evaluate_sum(&xs);
+
+    env::commit(&sum);
+}
@@ -0,0 +1,15 @@
+#![no_main]
+#![no_std]
+
+use polynomial_core::evaluate;
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let x: u64 = env::read();
+    let threshold: u64 = env::read();
+    let y = evaluate(x);
+
+    // y itself stays private, only the comparison is committed
+    env::commit(&(y.map(|y| y < threshold), threshold));
+}
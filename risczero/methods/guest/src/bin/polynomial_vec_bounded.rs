@@ -0,0 +1,28 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use polynomial_core::{evaluate, PolynomialResult};
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let xs: Vec<u64> = env::read();
+    let bounds: Vec<u64> = env::read();
+    assert_eq!(xs.len(), bounds.len(), "each x needs a bound");
+
+    // like PolynomialResult::evaluate, with the bound of each element
+    let results: Vec<PolynomialResult> = xs
+        .iter()
+        .zip(&bounds)
+        .map(|(&x, &bound)| match evaluate(x) {
+            None => PolynomialResult::Overflow,
+            Some(_) if x > bound => PolynomialResult::TooLarge,
+            Some(y) => PolynomialResult::Ok(y),
+        })
+        .collect();
+
+    env::commit(&results);
+}